@@ -0,0 +1,22 @@
+use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::{ActorError, EventBuilder};
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::ActorID;
+
+/// Indicates a movement of tokens between holders, in the style of an FRC-46 transfer.
+/// Minted tokens have no source, and burnt or destroyed tokens have no destination.
+pub fn transfer(
+    rt: &impl Runtime,
+    from: Option<ActorID>,
+    to: Option<ActorID>,
+    amount: &TokenAmount,
+) -> Result<(), ActorError> {
+    rt.emit_event(
+        &EventBuilder::new()
+            .typ("transfer")
+            .field_indexed("from", &from)
+            .field_indexed("to", &to)
+            .field("amount", amount)
+            .build()?,
+    )
+}
@@ -32,6 +32,7 @@ pub use self::types::*;
 #[cfg(feature = "fil-actor")]
 fil_actors_runtime::wasm_trampoline!(Actor);
 
+mod emit;
 mod state;
 pub mod testing;
 mod types;
@@ -190,7 +191,10 @@ impl Actor {
         let syscalls = SyscallProvider { rt };
         let intermediate = hook.call(&as_actor_runtime(&syscalls)).actor_result()?;
         let runtime = ActorRuntime::new(&syscalls, syscalls.rt.store());
-        as_token(&mut st, &runtime).mint_return(intermediate).actor_result()
+        let ret = as_token(&mut st, &runtime).mint_return(intermediate).actor_result()?;
+
+        emit::transfer(rt, None, Some(resolve_id(rt, &params.to)?), &params.amount)?;
+        Ok(ret)
     }
 
     /// Destroys data cap tokens for an address (a verified client).
@@ -198,18 +202,22 @@ impl Actor {
     /// This method is not part of the fungible token standard, and is named distinctly from
     /// "burn" to reflect that distinction.
     pub fn destroy(rt: &impl Runtime, params: DestroyParams) -> Result<BurnReturn, ActorError> {
-        rt.transaction(|st: &mut State, rt| {
-            // Only the governor can destroy datacap tokens on behalf of a holder.
-            rt.validate_immediate_caller_is(std::iter::once(&st.governor))?;
+        let ret = rt
+            .transaction(|st: &mut State, rt| {
+                // Only the governor can destroy datacap tokens on behalf of a holder.
+                rt.validate_immediate_caller_is(std::iter::once(&st.governor))?;
 
-            let syscalls = SyscallProvider { rt };
-            let runtime = ActorRuntime::new(&syscalls, syscalls.rt.store());
-            let mut token = as_token(st, &runtime);
-            // Burn tokens as if the holder had invoked burn() themselves.
-            // The governor doesn't need an allowance.
-            token.burn(&params.owner, &params.amount).actor_result()
-        })
-        .context("state transaction failed")
+                let syscalls = SyscallProvider { rt };
+                let runtime = ActorRuntime::new(&syscalls, syscalls.rt.store());
+                let mut token = as_token(st, &runtime);
+                // Burn tokens as if the holder had invoked burn() themselves.
+                // The governor doesn't need an allowance.
+                token.burn(&params.owner, &params.amount).actor_result()
+            })
+            .context("state transaction failed")?;
+
+        emit::transfer(rt, Some(resolve_id(rt, &params.owner)?), None, &params.amount)?;
+        Ok(ret)
     }
 
     /// Transfers data cap tokens to an address.
@@ -260,7 +268,10 @@ impl Actor {
         let syscalls = SyscallProvider { rt };
         let intermediate = hook.call(&as_actor_runtime(&syscalls)).actor_result()?;
         let runtime = ActorRuntime::new(&syscalls, syscalls.rt.store());
-        as_token(&mut st, &runtime).transfer_return(intermediate).actor_result()
+        let ret = as_token(&mut st, &runtime).transfer_return(intermediate).actor_result()?;
+
+        emit::transfer(rt, Some(from.id().unwrap()), Some(to), &params.amount)?;
+        Ok(ret)
     }
 
     /// Transfers data cap tokens between addresses.
@@ -312,7 +323,10 @@ impl Actor {
         let syscalls = SyscallProvider { rt };
         let intermediate = hook.call(&as_actor_runtime(&syscalls)).actor_result()?;
         let runtime = ActorRuntime::new(&syscalls, syscalls.rt.store());
-        as_token(&mut st, &runtime).transfer_from_return(intermediate).actor_result()
+        let ret = as_token(&mut st, &runtime).transfer_from_return(intermediate).actor_result()?;
+
+        emit::transfer(rt, Some(resolve_id(rt, &from)?), Some(to), &params.amount)?;
+        Ok(ret)
     }
 
     pub fn increase_allowance(
@@ -379,13 +393,17 @@ impl Actor {
         rt.validate_immediate_caller_accept_any()?;
         let owner = &rt.message().caller();
 
-        rt.transaction(|st: &mut State, rt| {
-            let syscalls = SyscallProvider { rt };
-            let runtime = ActorRuntime::new(&syscalls, syscalls.rt.store());
-            let mut token = as_token(st, &runtime);
-            token.burn(owner, &params.amount).actor_result()
-        })
-        .context("state transaction failed")
+        let ret = rt
+            .transaction(|st: &mut State, rt| {
+                let syscalls = SyscallProvider { rt };
+                let runtime = ActorRuntime::new(&syscalls, syscalls.rt.store());
+                let mut token = as_token(st, &runtime);
+                token.burn(owner, &params.amount).actor_result()
+            })
+            .context("state transaction failed")?;
+
+        emit::transfer(rt, Some(owner.id().unwrap()), None, &params.amount)?;
+        Ok(ret)
     }
 
     pub fn burn_from(
@@ -396,16 +414,27 @@ impl Actor {
         let operator = &rt.message().caller();
         let owner = &params.owner;
 
-        rt.transaction(|st: &mut State, rt| {
-            let syscalls = SyscallProvider { rt };
-            let runtime = ActorRuntime::new(&syscalls, syscalls.rt.store());
-            let mut token = as_token(st, &runtime);
-            token.burn_from(operator, owner, &params.amount).actor_result()
-        })
-        .context("state transaction failed")
+        let ret = rt
+            .transaction(|st: &mut State, rt| {
+                let syscalls = SyscallProvider { rt };
+                let runtime = ActorRuntime::new(&syscalls, syscalls.rt.store());
+                let mut token = as_token(st, &runtime);
+                token.burn_from(operator, owner, &params.amount).actor_result()
+            })
+            .context("state transaction failed")?;
+
+        emit::transfer(rt, Some(resolve_id(rt, owner)?), None, &params.amount)?;
+        Ok(ret)
     }
 }
 
+// Resolves the address of a token holder, which must exist, to its actor ID.
+fn resolve_id(rt: &impl Runtime, address: &Address) -> Result<ActorID, ActorError> {
+    rt.resolve_address(address).with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+        format!("failed to resolve token holder {}", address)
+    })
+}
+
 /// Implementation of the token library's messenger trait in terms of the built-in actors'
 /// runtime library.
 struct SyscallProvider<'a, RT> {
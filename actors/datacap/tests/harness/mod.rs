@@ -9,7 +9,7 @@ use fvm_ipld_encoding::RawBytes;
 use fvm_shared::address::Address;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
-use fvm_shared::MethodNum;
+use fvm_shared::{ActorID, MethodNum};
 use num_traits::Zero;
 
 use fil_actor_datacap::testing::check_state_invariants;
@@ -18,7 +18,8 @@ use fil_actors_runtime::cbor::serialize;
 use fil_actors_runtime::runtime::Runtime;
 use fil_actors_runtime::test_utils::*;
 use fil_actors_runtime::{
-    ActorError, DATACAP_TOKEN_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
+    ActorError, EventBuilder, DATACAP_TOKEN_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    VERIFIED_REGISTRY_ACTOR_ADDR,
 };
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 
@@ -95,6 +96,8 @@ impl Harness {
             ExitCode::OK,
         );
 
+        expect_transfer_event(rt, None, to.id().ok(), amount);
+
         let params = MintParams { to: *to, amount: amount.clone(), operators };
         rt.set_caller(*VERIFREG_ACTOR_CODE_ID, VERIFIED_REGISTRY_ACTOR_ADDR);
         let ret = rt.call::<DataCapActor>(
@@ -115,6 +118,7 @@ impl Harness {
         rt.expect_validate_caller_addr(vec![VERIFIED_REGISTRY_ACTOR_ADDR]);
 
         let params = DestroyParams { owner: *owner, amount: amount.clone() };
+        expect_transfer_event(rt, owner.id().ok(), None, amount);
 
         rt.set_caller(*VERIFREG_ACTOR_CODE_ID, VERIFIED_REGISTRY_ACTOR_ADDR);
         let ret = rt.call::<DataCapActor>(
@@ -162,6 +166,8 @@ impl Harness {
             ExitCode::OK,
         );
 
+        expect_transfer_event(rt, from.id().ok(), to.id().ok(), amount);
+
         let params = TransferParams { to: *to, amount: amount.clone(), operator_data };
         let ret = rt.call::<DataCapActor>(
             Method::TransferExported as MethodNum,
@@ -209,6 +215,8 @@ impl Harness {
             ExitCode::OK,
         );
 
+        expect_transfer_event(rt, from.id().ok(), to.id().ok(), amount);
+
         let params =
             TransferFromParams { to: *to, from: *from, amount: amount.clone(), operator_data };
         let ret = rt.call::<DataCapActor>(
@@ -259,3 +267,20 @@ impl Harness {
         acc.assert_empty();
     }
 }
+
+pub fn expect_transfer_event(
+    rt: &MockRuntime,
+    from: Option<ActorID>,
+    to: Option<ActorID>,
+    amount: &TokenAmount,
+) {
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ("transfer")
+            .field_indexed("from", &from)
+            .field_indexed("to", &to)
+            .field("amount", amount)
+            .build()
+            .unwrap(),
+    );
+}
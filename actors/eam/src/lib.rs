@@ -37,6 +37,9 @@ pub enum Method {
     Create = 2,
     Create2 = 3,
     CreateExternal = 4,
+    // Method numbers derived from FRC-0042 standards
+    ComputeCreateAddressExported = frc42_dispatch::method_hash!("ComputeCreateAddress"),
+    ComputeCreate2AddressExported = frc42_dispatch::method_hash!("ComputeCreate2Address"),
 }
 
 /// Compute the a new actor address using the EVM's CREATE rules.
@@ -87,6 +90,44 @@ pub struct Return {
     pub eth_address: EthAddress,
 }
 
+#[derive(Serialize_tuple, Deserialize_tuple)]
+pub struct ComputeCreateAddressParams {
+    /// The Ethereum address of the creating contract.
+    pub from: EthAddress,
+    /// The creating contract's nonce at the time of the CREATE.
+    pub nonce: u64,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple)]
+pub struct ComputeCreate2AddressParams {
+    /// The Ethereum address of the creating contract.
+    pub from: EthAddress,
+    #[serde(with = "strict_bytes")]
+    pub initcode: Vec<u8>,
+    #[serde(with = "strict_bytes")]
+    pub salt: [u8; 32],
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, PartialEq, Eq)]
+pub struct ComputeAddressReturn {
+    pub eth_address: EthAddress,
+    /// The f4 address of the new actor, in the EAM's namespace.
+    pub f4_address: Address,
+}
+
+impl ComputeAddressReturn {
+    fn new(eth_address: EthAddress) -> Result<Self, ActorError> {
+        // Mirror `create_actor`, which refuses to deploy at these addresses.
+        if !can_assign_address(&eth_address) {
+            return Err(ActorError::forbidden(
+                "cannot create address with a reserved prefix".into(),
+            ));
+        }
+        let f4_address = Address::new_delegated(EAM_ACTOR_ID, &eth_address.0).unwrap();
+        Ok(Self { eth_address, f4_address })
+    }
+}
+
 pub type CreateReturn = Return;
 pub type Create2Return = Return;
 pub type CreateExternalReturn = Return;
@@ -286,6 +327,35 @@ impl EamActor {
         let eth_addr = compute_address_create_external(rt, &stable_addr);
         create_actor(rt, owner_addr, eth_addr, params.0)
     }
+
+    /// Computes the addresses a `Create` call by `from` with the given nonce would deploy to,
+    /// without creating anything.
+    ///
+    /// Permissions: May be called by any actor.
+    pub fn compute_create_address(
+        rt: &impl Runtime,
+        params: ComputeCreateAddressParams,
+    ) -> Result<ComputeAddressReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        ComputeAddressReturn::new(compute_address_create(rt, &params.from, params.nonce))
+    }
+
+    /// Computes the addresses a `Create2` call by `from` with the given initcode and salt would
+    /// deploy to, without creating anything.
+    ///
+    /// Permissions: May be called by any actor.
+    pub fn compute_create2_address(
+        rt: &impl Runtime,
+        params: ComputeCreate2AddressParams,
+    ) -> Result<ComputeAddressReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        ComputeAddressReturn::new(compute_address_create2(
+            rt,
+            &params.from,
+            &params.salt,
+            &params.initcode,
+        ))
+    }
 }
 
 impl ActorCode for EamActor {
@@ -300,6 +370,8 @@ impl ActorCode for EamActor {
         Create => create,
         Create2 => create2,
         CreateExternal => create_external,
+        ComputeCreateAddressExported => compute_create_address,
+        ComputeCreate2AddressExported => compute_create2_address,
     }
 }
 
@@ -1,16 +1,19 @@
 use eam::ext::evm::RESURRECT_METHOD;
 use eam::ext::init::{Exec4Params, Exec4Return, EXEC4_METHOD};
-use eam::{compute_address_create, Create2Params, CreateParams, Return};
+use eam::{
+    compute_address_create, ComputeAddressReturn, ComputeCreate2AddressParams,
+    ComputeCreateAddressParams, Create2Params, CreateParams, Return,
+};
 use fil_actor_eam as eam;
 use fil_actor_eam::CreateExternalParams;
 use fil_actors_evm_shared::address::EthAddress;
 use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::Primitives;
 use fil_actors_runtime::test_utils::{
-    expect_empty, MockRuntime, ETHACCOUNT_ACTOR_CODE_ID, EVM_ACTOR_CODE_ID,
+    expect_empty, MockRuntime, ACCOUNT_ACTOR_CODE_ID, ETHACCOUNT_ACTOR_CODE_ID, EVM_ACTOR_CODE_ID,
     PLACEHOLDER_ACTOR_CODE_ID, SYSTEM_ACTOR_CODE_ID,
 };
-use fil_actors_runtime::{INIT_ACTOR_ADDR, SYSTEM_ACTOR_ADDR};
+use fil_actors_runtime::{ActorError, INIT_ACTOR_ADDR, SYSTEM_ACTOR_ADDR};
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::RawBytes;
 use fvm_shared::address::Address;
@@ -266,6 +269,135 @@ fn call_create2() {
     rt.verify();
 }
 
+/// Calls an exported address computation method as an account, which may call it.
+fn compute_address<P: serde::Serialize>(
+    rt: &MockRuntime,
+    method: eam::Method,
+    params: &P,
+) -> Result<ComputeAddressReturn, ActorError> {
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(1000));
+    rt.expect_validate_caller_any();
+    // Nothing is sent or created.
+    let result =
+        rt.call::<eam::EamActor>(method as u64, IpldBlock::serialize_cbor(params).unwrap());
+    rt.verify();
+    Ok(result?.unwrap().deserialize().unwrap())
+}
+
+#[test]
+fn compute_create_address() {
+    let rt = construct_and_verify();
+
+    // Contract addresses deployed by 0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0 on Ethereum.
+    let from = EthAddress(hex_literal::hex!("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0"));
+    let expected = [
+        hex_literal::hex!("cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d"),
+        hex_literal::hex!("343c43a37d37dff08ae8c4a11544c718abb4fcf8"),
+        hex_literal::hex!("f778b86fa74e846c4f0a1fbd1335fe81c00a0c91"),
+        hex_literal::hex!("fffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c"),
+    ];
+    for (nonce, expected) in expected.into_iter().enumerate() {
+        let params = ComputeCreateAddressParams { from, nonce: nonce as u64 };
+        let result =
+            compute_address(&rt, eam::Method::ComputeCreateAddressExported, &params).unwrap();
+        assert_eq!(
+            result,
+            ComputeAddressReturn {
+                eth_address: EthAddress(expected),
+                f4_address: Address::new_delegated(10, &expected).unwrap(),
+            }
+        );
+    }
+}
+
+#[test]
+fn compute_create2_address() {
+    let rt = construct_and_verify();
+
+    // The examples of EIP-1014.
+    let cases: [(_, [u8; 32], &[u8], _); 7] = [
+        (
+            hex_literal::hex!("0000000000000000000000000000000000000000"),
+            [0; 32],
+            &hex_literal::hex!("00"),
+            hex_literal::hex!("4d1a2e2bb4f88f0250f26ffff098b0b30b26bf38"),
+        ),
+        (
+            hex_literal::hex!("deadbeef00000000000000000000000000000000"),
+            [0; 32],
+            &hex_literal::hex!("00"),
+            hex_literal::hex!("b928f69bb1d91cd65274e3c79d8986362984fda3"),
+        ),
+        (
+            hex_literal::hex!("deadbeef00000000000000000000000000000000"),
+            hex_literal::hex!("000000000000000000000000feed000000000000000000000000000000000000"),
+            &hex_literal::hex!("00"),
+            hex_literal::hex!("d04116cdd17bebe565eb2422f2497e06cc1c9833"),
+        ),
+        (
+            hex_literal::hex!("0000000000000000000000000000000000000000"),
+            [0; 32],
+            &hex_literal::hex!("deadbeef"),
+            hex_literal::hex!("70f2b2914a2a4b783faefb75f459a580616fcb5e"),
+        ),
+        (
+            hex_literal::hex!("00000000000000000000000000000000deadbeef"),
+            hex_literal::hex!("00000000000000000000000000000000000000000000000000000000cafebabe"),
+            &hex_literal::hex!("deadbeef"),
+            hex_literal::hex!("60f3f640a8508fc6a86d45df051962668e1e8ac7"),
+        ),
+        (
+            hex_literal::hex!("00000000000000000000000000000000deadbeef"),
+            hex_literal::hex!("00000000000000000000000000000000000000000000000000000000cafebabe"),
+            &hex_literal::hex!(
+                "deadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+            ),
+            hex_literal::hex!("1d8bfdc5d46dc4f61d6b6115972536ebe6a8854c"),
+        ),
+        (
+            hex_literal::hex!("0000000000000000000000000000000000000000"),
+            [0; 32],
+            &[],
+            hex_literal::hex!("e33c0c7f7df4809055c3eba6c09cfe4baf1bd9e0"),
+        ),
+    ];
+    for (from, salt, initcode, expected) in cases {
+        let params =
+            ComputeCreate2AddressParams { from: EthAddress(from), initcode: initcode.into(), salt };
+        let result =
+            compute_address(&rt, eam::Method::ComputeCreate2AddressExported, &params).unwrap();
+        assert_eq!(
+            result,
+            ComputeAddressReturn {
+                eth_address: EthAddress(expected),
+                f4_address: Address::new_delegated(10, &expected).unwrap(),
+            }
+        );
+    }
+}
+
+#[test]
+fn compute_address_forbidden() {
+    let mut rt = construct_and_verify();
+    // Hash everything to a digest ending in an ID address, which can't be assigned.
+    rt.hash_func = Box::new(|_, _| {
+        let mut digest = [0u8; 64];
+        digest[12] = 0xff;
+        digest[31] = 100;
+        (digest, 32)
+    });
+
+    let from = EthAddress(hex_literal::hex!("CAFEB0BA00000000000000000000000000000000"));
+    let params = ComputeCreateAddressParams { from, nonce: 0 };
+    let err = compute_address(&rt, eam::Method::ComputeCreateAddressExported, &params).unwrap_err();
+    assert_eq!(err.exit_code(), ExitCode::USR_FORBIDDEN);
+
+    let params = ComputeCreate2AddressParams { from, initcode: vec![0xff], salt: [0; 32] };
+    let err =
+        compute_address(&rt, eam::Method::ComputeCreate2AddressExported, &params).unwrap_err();
+    assert_eq!(err.exit_code(), ExitCode::USR_FORBIDDEN);
+}
+
 pub fn construct_and_verify() -> MockRuntime {
     let rt = MockRuntime { receiver: Address::new_id(10), ..Default::default() };
 
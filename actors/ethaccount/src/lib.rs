@@ -2,6 +2,8 @@ pub mod types;
 
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::address::Payload;
+use fvm_shared::crypto::hash::SupportedHashes;
+use fvm_shared::crypto::signature::SECP_SIG_LEN;
 use fvm_shared::{MethodNum, METHOD_CONSTRUCTOR};
 use num_derive::FromPrimitive;
 
@@ -11,6 +13,8 @@ use fil_actors_runtime::{
     SYSTEM_ACTOR_ADDR,
 };
 
+use crate::types::{AuthenticateMessageParams, AuthenticateMessageReturn, MessageFormat};
+
 #[cfg(feature = "fil-actor")]
 fil_actors_runtime::wasm_trampoline!(EthAccountActor);
 
@@ -19,8 +23,16 @@ fil_actors_runtime::wasm_trampoline!(EthAccountActor);
 #[repr(u64)]
 pub enum Method {
     Constructor = METHOD_CONSTRUCTOR,
+    AuthenticateMessageExported = frc42_dispatch::method_hash!("AuthenticateMessage"),
 }
 
+/// The prefix of an EIP-712 encoded message: `0x19 0x01 || domainSeparator || hashStruct(message)`.
+const EIP712_PREFIX: [u8; 2] = [0x19, 0x01];
+/// The length of an EIP-712 encoded message.
+const EIP712_MESSAGE_LEN: usize = 66;
+/// The prefix of an EIP-191 "personal_sign" message, followed by the decimal message length.
+const EIP191_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n";
+
 /// Ethereum Account actor.
 pub struct EthAccountActor;
 
@@ -50,6 +62,54 @@ impl EthAccountActor {
         Ok(())
     }
 
+    /// Authenticates whether the provided signature is valid for the provided message, and was
+    /// made by the key behind this account's Ethereum address.
+    ///
+    /// The signature must be a 65 byte secp256k1 signature, with a recovery id of 0/1 or 27/28.
+    /// The message is signed as an EIP-191 "personal_sign" message, unless the params say it's
+    /// an EIP-712 encoded message.
+    pub fn authenticate_message(
+        rt: &impl Runtime,
+        params: AuthenticateMessageParams,
+    ) -> Result<AuthenticateMessageReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+
+        let eth_address = match rt
+            .lookup_delegated_address(rt.message().receiver().id().unwrap())
+            .map(|a| *a.payload())
+        {
+            Some(Payload::Delegated(da)) if da.namespace() == EAM_ACTOR_ID => {
+                da.subaddress().to_vec()
+            }
+            _ => return Err(actor_error!(illegal_state; "account has no Ethereum address")),
+        };
+
+        let mut signature: [u8; SECP_SIG_LEN] =
+            params.signature.as_slice().try_into().map_err(|_| {
+                actor_error!(illegal_argument; "invalid signature length {}", params.signature.len())
+            })?;
+        // Ethereum signatures usually carry a recovery id offset by 27.
+        if signature[SECP_SIG_LEN - 1] >= 27 {
+            signature[SECP_SIG_LEN - 1] -= 27;
+        }
+
+        let digest = message_digest(rt, &params.message, params.format)?;
+        let pubkey = rt.recover_secp_public_key(&digest, &signature).map_err(|e| {
+            actor_error!(illegal_argument; "failed to authenticate message, signature invalid: {}", e)
+        })?;
+        // The Ethereum address is the last 20 bytes of the hash of the uncompressed public key,
+        // without its 0x04 prefix.
+        let signer = rt.hash(SupportedHashes::Keccak256, &pubkey[1..]);
+        if signer[12..] != eth_address[..] {
+            return Err(actor_error!(
+                illegal_argument;
+                "failed to authenticate message, signature invalid"
+            ));
+        }
+
+        Ok(AuthenticateMessageReturn { authenticated: true })
+    }
+
     // Always succeeds, accepting any transfers.
     pub fn fallback(
         rt: &impl Runtime,
@@ -74,6 +134,32 @@ impl ActorCode for EthAccountActor {
 
     actor_dispatch! {
         Constructor => constructor,
+        AuthenticateMessageExported => authenticate_message,
         _ => fallback [raw],
     }
 }
+
+/// Computes the digest signed for a message of the given format.
+fn message_digest(
+    rt: &impl Runtime,
+    message: &[u8],
+    format: MessageFormat,
+) -> Result<[u8; 32], ActorError> {
+    let digest = match format {
+        MessageFormat::Eip191 => {
+            let len = message.len().to_string();
+            rt.hash(SupportedHashes::Keccak256, &[EIP191_PREFIX, len.as_bytes(), message].concat())
+        }
+        MessageFormat::Eip712 => {
+            if message.len() != EIP712_MESSAGE_LEN || !message.starts_with(&EIP712_PREFIX) {
+                return Err(actor_error!(
+                    illegal_argument;
+                    "invalid EIP-712 message of length {}",
+                    message.len()
+                ));
+            }
+            rt.hash(SupportedHashes::Keccak256, message)
+        }
+    };
+    Ok(digest.try_into().unwrap())
+}
@@ -1,10 +1,69 @@
-use fvm_ipld_encoding::strict_bytes;
+use std::fmt;
+
+use fvm_ipld_encoding::repr::*;
 use fvm_ipld_encoding::tuple::*;
+use fvm_ipld_encoding::{strict_bytes, BytesDe};
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
 
-#[derive(Debug, Serialize_tuple, Deserialize_tuple)]
+/// How the message passed to `AuthenticateMessage` was signed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum MessageFormat {
+    /// An EIP-191 "personal_sign" message: the signature is over the message prefixed with
+    /// `"\x19Ethereum Signed Message:\n"` and its decimal length.
+    #[default]
+    Eip191 = 0,
+    /// An EIP-712 encoded message, `0x19 0x01 || domainSeparator || hashStruct(message)`, whose
+    /// hash is signed as is.
+    Eip712 = 1,
+}
+
+#[derive(Debug, Serialize_tuple)]
 pub struct AuthenticateMessageParams {
     #[serde(with = "strict_bytes")]
     pub signature: Vec<u8>,
     #[serde(with = "strict_bytes")]
     pub message: Vec<u8>,
+    /// The format of the message. Absent from the FRC-0044 params sent by other actors, in which
+    /// case the message is an EIP-191 message.
+    pub format: MessageFormat,
+}
+
+/// Deserialize the params like a tuple, accepting the two field FRC-0044 params.
+impl<'de> Deserialize<'de> for AuthenticateMessageParams {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ParamsVisitor;
+
+        impl<'de> Visitor<'de> for ParamsVisitor {
+            type Value = AuthenticateMessageParams;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a signature, a message and an optional message format")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<AuthenticateMessageParams, A::Error> {
+                let BytesDe(signature) =
+                    seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let BytesDe(message) =
+                    seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let format = seq.next_element()?.unwrap_or_default();
+                Ok(AuthenticateMessageParams { signature, message, format })
+            }
+        }
+
+        deserializer.deserialize_seq(ParamsVisitor)
+    }
+}
+
+#[derive(Debug, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct AuthenticateMessageReturn {
+    pub authenticated: bool,
 }
@@ -3,10 +3,16 @@ mod util;
 use crate::util::*;
 use fvm_actor_utils::receiver::UniversalReceiverParams;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
-use fvm_ipld_encoding::RawBytes;
+use fvm_ipld_encoding::{BytesSer, RawBytes};
 use fvm_shared::address::Address;
 
+use fil_actor_ethaccount::types::{
+    AuthenticateMessageParams, AuthenticateMessageReturn, MessageFormat,
+};
 use fil_actor_ethaccount::{EthAccountActor, Method};
+use fil_actors_runtime::runtime::Primitives;
+use fil_actors_runtime::test_utils::MockRuntime;
+use fvm_shared::crypto::hash::SupportedHashes;
 use fvm_shared::error::ExitCode;
 use fvm_shared::MethodNum;
 
@@ -46,3 +52,126 @@ fn token_receiver() {
         .unwrap();
     assert!(ret.is_none());
 }
+
+const SECRET_KEY: [u8; 32] = [7; 32];
+
+/// Returns the Ethereum address of the test key.
+fn eth_address(rt: &MockRuntime) -> [u8; 20] {
+    let key = libsecp256k1::SecretKey::parse(&SECRET_KEY).unwrap();
+    let pubkey = libsecp256k1::PublicKey::from_secret_key(&key).serialize();
+    rt.hash(SupportedHashes::Keccak256, &pubkey[1..])[12..].try_into().unwrap()
+}
+
+/// Signs a digest with the test key, with the recovery id offset by `v_offset`.
+fn sign(digest: &[u8], v_offset: u8) -> Vec<u8> {
+    let key = libsecp256k1::SecretKey::parse(&SECRET_KEY).unwrap();
+    let message = libsecp256k1::Message::parse_slice(digest).unwrap();
+    let (sig, recovery_id) = libsecp256k1::sign(&message, &key);
+    let mut signature = sig.serialize().to_vec();
+    signature.push(recovery_id.serialize() + v_offset);
+    signature
+}
+
+fn authenticate(
+    rt: &MockRuntime,
+    signature: Vec<u8>,
+    message: Vec<u8>,
+    format: MessageFormat,
+) -> Result<AuthenticateMessageReturn, fil_actors_runtime::ActorError> {
+    let params = AuthenticateMessageParams { signature, message, format };
+    authenticate_raw(rt, IpldBlock::serialize_cbor(&params).unwrap())
+}
+
+fn authenticate_raw(
+    rt: &MockRuntime,
+    params: Option<IpldBlock>,
+) -> Result<AuthenticateMessageReturn, fil_actors_runtime::ActorError> {
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(1234));
+    rt.expect_validate_caller_any();
+    let ret = rt.call::<EthAccountActor>(Method::AuthenticateMessageExported as MethodNum, params);
+    rt.verify();
+    rt.reset();
+    ret.map(|r| r.unwrap().deserialize().unwrap())
+}
+
+#[test]
+fn authenticate_eip191_message() {
+    let rt = new_runtime();
+    let rt = setup_with_eth_address(&eth_address(&rt));
+
+    let message = b"hello filecoin".to_vec();
+    let digest =
+        rt.hash(SupportedHashes::Keccak256, b"\x19Ethereum Signed Message:\n14hello filecoin");
+
+    // Both raw and Ethereum-style recovery ids are accepted.
+    for v_offset in [0, 27] {
+        let ret =
+            authenticate(&rt, sign(&digest, v_offset), message.clone(), MessageFormat::Eip191)
+                .unwrap();
+        assert!(ret.authenticated);
+    }
+
+    // The FRC-0044 params sent by other actors have no format, and are EIP-191 messages.
+    let signature = sign(&digest, 0);
+    let params = (BytesSer(&signature), BytesSer(&message));
+    let ret = authenticate_raw(&rt, IpldBlock::serialize_cbor(&params).unwrap()).unwrap();
+    assert!(ret.authenticated);
+
+    // A signature over a different message is rejected.
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        "signature invalid",
+        authenticate(&rt, sign(&digest, 0), b"hello ethereum".to_vec(), MessageFormat::Eip191),
+    );
+}
+
+#[test]
+fn authenticate_eip712_message() {
+    let rt = new_runtime();
+    let rt = setup_with_eth_address(&eth_address(&rt));
+
+    // 0x1901 || domainSeparator || hashStruct(message)
+    let message = [&[0x19, 0x01][..], &[1; 32], &[2; 32]].concat();
+    let digest = rt.hash(SupportedHashes::Keccak256, &message);
+
+    let ret = authenticate(&rt, sign(&digest, 27), message.clone(), MessageFormat::Eip712).unwrap();
+    assert!(ret.authenticated);
+
+    // The format isn't inferred from the message: as an EIP-191 message, it has another digest.
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        "signature invalid",
+        authenticate(&rt, sign(&digest, 27), message.clone(), MessageFormat::Eip191),
+    );
+
+    // EIP-712 messages must be 0x1901 followed by two hashes.
+    for message in [message[..65].to_vec(), [&[0x19, 0x00][..], &message[2..]].concat()] {
+        let digest = rt.hash(SupportedHashes::Keccak256, &message);
+        expect_abort_contains_message(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            "invalid EIP-712 message",
+            authenticate(&rt, sign(&digest, 27), message, MessageFormat::Eip712),
+        );
+    }
+}
+
+#[test]
+fn authenticate_rejects_other_signers() {
+    // The account's address doesn't match the signing key.
+    let rt = setup();
+
+    let message = [&[0x19, 0x01][..], &[1; 32], &[2; 32]].concat();
+    let digest = rt.hash(SupportedHashes::Keccak256, &message);
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        "signature invalid",
+        authenticate(&rt, sign(&digest, 0), message.clone(), MessageFormat::Eip712),
+    );
+
+    // Malformed signatures are rejected.
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        "invalid signature length",
+        authenticate(&rt, vec![0; 64], message, MessageFormat::Eip712),
+    );
+}
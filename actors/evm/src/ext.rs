@@ -28,3 +28,165 @@ pub mod eam {
         pub eth_address: EthAddress,
     }
 }
+
+pub mod market {
+    use cid::Cid;
+    use fvm_ipld_encoding::tuple::*;
+    use fvm_shared::clock::ChainEpoch;
+    use fvm_shared::econ::TokenAmount;
+    use fvm_shared::piece::PaddedPieceSize;
+    use fvm_shared::ActorID;
+
+    pub const GET_BALANCE_METHOD: u64 = frc42_dispatch::method_hash!("GetBalance");
+    pub const GET_DEAL_DATA_COMMITMENT_METHOD: u64 =
+        frc42_dispatch::method_hash!("GetDealDataCommitment");
+    pub const GET_DEAL_CLIENT_METHOD: u64 = frc42_dispatch::method_hash!("GetDealClient");
+    pub const GET_DEAL_PROVIDER_METHOD: u64 = frc42_dispatch::method_hash!("GetDealProvider");
+    pub const GET_DEAL_TERM_METHOD: u64 = frc42_dispatch::method_hash!("GetDealTerm");
+    pub const GET_DEAL_TOTAL_PRICE_METHOD: u64 = frc42_dispatch::method_hash!("GetDealTotalPrice");
+    pub const GET_DEAL_CLIENT_COLLATERAL_METHOD: u64 =
+        frc42_dispatch::method_hash!("GetDealClientCollateral");
+    pub const GET_DEAL_PROVIDER_COLLATERAL_METHOD: u64 =
+        frc42_dispatch::method_hash!("GetDealProviderCollateral");
+    pub const GET_DEAL_VERIFIED_METHOD: u64 = frc42_dispatch::method_hash!("GetDealVerified");
+    pub const GET_DEAL_ACTIVATION_METHOD: u64 = frc42_dispatch::method_hash!("GetDealActivation");
+
+    #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+    pub struct GetBalanceReturn {
+        pub balance: TokenAmount,
+        pub locked: TokenAmount,
+    }
+
+    #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+    pub struct GetDealDataCommitmentReturn {
+        pub data: Cid,
+        pub size: PaddedPieceSize,
+    }
+
+    #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+    #[serde(transparent)]
+    pub struct GetDealPartyReturn {
+        pub id: ActorID,
+    }
+
+    #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+    pub struct GetDealTermReturn {
+        pub start: ChainEpoch,
+        pub duration: ChainEpoch,
+    }
+
+    #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+    #[serde(transparent)]
+    pub struct GetDealAmountReturn {
+        pub amount: TokenAmount,
+    }
+
+    #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+    #[serde(transparent)]
+    pub struct GetDealVerifiedReturn {
+        pub verified: bool,
+    }
+
+    #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+    pub struct GetDealActivationReturn {
+        pub activated: ChainEpoch,
+        pub terminated: ChainEpoch,
+    }
+}
+
+pub mod miner {
+    use fvm_ipld_encoding::tuple::*;
+    use fvm_shared::econ::TokenAmount;
+
+    pub const GET_SECTOR_SIZE_METHOD: u64 = frc42_dispatch::method_hash!("GetSectorSize");
+    pub const GET_AVAILABLE_BALANCE_METHOD: u64 =
+        frc42_dispatch::method_hash!("GetAvailableBalance");
+    pub const IS_CONTROLLING_ADDRESS_METHOD: u64 =
+        frc42_dispatch::method_hash!("IsControllingAddress");
+
+    #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+    #[serde(transparent)]
+    pub struct GetSectorSizeReturn {
+        pub sector_size: u64,
+    }
+
+    #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+    #[serde(transparent)]
+    pub struct GetAvailableBalanceReturn {
+        pub available_balance: TokenAmount,
+    }
+
+    #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+    #[serde(transparent)]
+    pub struct IsControllingAddressReturn {
+        pub is_controlling: bool,
+    }
+}
+
+pub mod power {
+    use fvm_ipld_encoding::tuple::*;
+    use fvm_shared::bigint::bigint_ser;
+    use fvm_shared::sector::StoragePower;
+
+    pub const NETWORK_RAW_POWER_METHOD: u64 = frc42_dispatch::method_hash!("NetworkRawPower");
+    pub const MINER_RAW_POWER_METHOD: u64 = frc42_dispatch::method_hash!("MinerRawPower");
+    pub const MINER_COUNT_METHOD: u64 = frc42_dispatch::method_hash!("MinerCount");
+    pub const MINER_CONSENSUS_COUNT_METHOD: u64 =
+        frc42_dispatch::method_hash!("MinerConsensusCount");
+
+    #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+    #[serde(transparent)]
+    pub struct NetworkRawPowerReturn {
+        #[serde(with = "bigint_ser")]
+        pub raw_byte_power: StoragePower,
+    }
+
+    #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+    pub struct MinerRawPowerReturn {
+        #[serde(with = "bigint_ser")]
+        pub raw_byte_power: StoragePower,
+        pub meets_consensus_minimum: bool,
+    }
+
+    #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+    #[serde(transparent)]
+    pub struct MinerCountReturn {
+        pub count: i64,
+    }
+}
+
+pub mod verifreg {
+    use cid::Cid;
+    use fil_actors_runtime::BatchReturn;
+    use fvm_ipld_encoding::tuple::*;
+    use fvm_shared::clock::ChainEpoch;
+    use fvm_shared::piece::PaddedPieceSize;
+    use fvm_shared::sector::SectorNumber;
+    use fvm_shared::ActorID;
+
+    pub const GET_CLAIMS_METHOD: u64 = frc42_dispatch::method_hash!("GetClaims");
+
+    #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+    pub struct GetClaimsParams {
+        pub provider: ActorID,
+        pub claim_ids: Vec<u64>,
+    }
+
+    #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+    pub struct Claim {
+        pub provider: ActorID,
+        pub client: ActorID,
+        pub data: Cid,
+        pub size: PaddedPieceSize,
+        pub term_min: ChainEpoch,
+        pub term_max: ChainEpoch,
+        pub term_start: ChainEpoch,
+        pub sector: SectorNumber,
+    }
+
+    #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+    pub struct GetClaimsReturn {
+        pub batch_info: BatchReturn,
+        pub claims: Vec<Claim>,
+    }
+}
@@ -73,6 +73,13 @@ macro_rules! def_opcodes {
             $(table[$code] = $op;)*
             table
         }
+
+        /// Opcode mnemonics, indexed by opcode.
+        pub const NAMES: [&str; 256] = {
+            let mut names = ["UNDEFINED"; 256];
+            $(names[$code] = stringify!($op);)*
+            names
+        };
         $(pub const $op: u8 = $code;)*
     }
 }
@@ -163,6 +170,9 @@ pub mod opcodes {
         0x59: MSIZE,
         0x5a: GAS,
         0x5b: JUMPDEST,
+        0x5c: TLOAD,
+        0x5d: TSTORE,
+        0x5e: MCOPY,
         0x5F: PUSH0,
         0x60: PUSH1,
         0x61: PUSH2,
@@ -256,6 +266,11 @@ impl<'r, 'a, RT: Runtime + 'r> Machine<'r, 'a, RT> {
     }
 
     pub fn execute(mut self) -> Result<Output, ActorError> {
+        #[cfg(not(feature = "fil-actor"))]
+        if super::tracer::is_active() {
+            return self.execute_traced();
+        }
+
         while self.pc < self.bytecode.len() {
             // This is faster than the question mark operator, and speed counts here.
             #[allow(clippy::question_mark)]
@@ -267,6 +282,44 @@ impl<'r, 'a, RT: Runtime + 'r> Machine<'r, 'a, RT> {
         Ok(self.output)
     }
 
+    /// Like `execute`, but records every step with the tracer.
+    #[cfg(not(feature = "fil-actor"))]
+    fn execute_traced(mut self) -> Result<Output, ActorError> {
+        use super::tracer::{self, DepthGuard, StructLog};
+
+        let depth = DepthGuard::enter();
+        while self.pc < self.bytecode.len() {
+            let pc = self.pc;
+            let op = self.bytecode[pc];
+            let stack = tracer::stack_top(&self.state.stack);
+            let mem_size = self.state.memory.len() as u64;
+            let gas = self.system.rt.gas_available();
+
+            // Record the step before executing it so that steps of nested calls come after it.
+            let index = tracer::record(StructLog {
+                pc: pc as u64,
+                op: opcodes::NAMES[op as usize],
+                gas,
+                gas_cost: 0,
+                depth: depth.depth,
+                stack,
+                mem_size,
+                error: None,
+            });
+            let result = self.step();
+            let gas_cost = gas.saturating_sub(self.system.rt.gas_available());
+            tracer::update(index, |step| {
+                step.gas_cost = gas_cost;
+                step.error = result.as_ref().err().map(|e| e.msg().to_string());
+            });
+            if let Err(e) = result {
+                return Err(e.wrap(format!("ABORT(pc={})", self.pc)));
+            }
+        }
+
+        Ok(self.output)
+    }
+
     #[inline(always)]
     // Note: pub only for unit test steps.
     pub(crate) fn step(&mut self) -> Result<(), ActorError> {
@@ -397,8 +450,11 @@ mod tests {
             MLOAD,
             MSTORE,
             MSTORE8,
+            MCOPY,
             SLOAD,
             SSTORE,
+            TLOAD,
+            TSTORE,
             LOG0,
             LOG1,
             LOG2,
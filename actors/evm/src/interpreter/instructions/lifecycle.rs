@@ -11,7 +11,7 @@ use fvm_shared::{address::Address, econ::TokenAmount};
 
 use crate::ext::eam;
 use crate::interpreter::Output;
-use crate::EVM_CONTRACT_SELFDESTRUCT_FAILED;
+use crate::{EIP_6780_NETWORK_VERSION, EVM_CONTRACT_SELFDESTRUCT_FAILED};
 
 use super::memory::{get_memory_region, MemoryRegion};
 use {
@@ -152,8 +152,12 @@ pub fn selfdestruct(
         },
     )?;
 
-    // Now mark ourselves as deleted.
-    system.mark_selfdestructed();
+    // Now mark ourselves as deleted. Under EIP-6780, only contracts created during the current
+    // top-level message are deleted; otherwise SELFDESTRUCT just sends away all funds.
+    if system.rt.network_version() < EIP_6780_NETWORK_VERSION || system.created_in_current_message()
+    {
+        system.mark_selfdestructed();
+    }
 
     // And "return".
     //
@@ -543,7 +547,7 @@ mod tests {
             m.step().expect("execution step failed");
             assert!(m.system.tombstone.is_some());
             assert_eq!(m.system.tombstone.unwrap(),
-                       crate::Tombstone {
+                       crate::TopLevelMessage {
                            origin: 1001,
                            nonce: 0,
                        });
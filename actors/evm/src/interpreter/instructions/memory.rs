@@ -134,6 +134,26 @@ pub fn mstore8(
     Ok(())
 }
 
+#[inline]
+pub fn mcopy(
+    state: &mut ExecutionState,
+    _system: &System<impl Runtime>,
+    dest_index: U256,
+    src_index: U256,
+    size: U256,
+) -> Result<(), ActorError> {
+    // Memory is expanded to cover both regions, even if only one extends past the current size.
+    let src_region = get_memory_region(&mut state.memory, src_index, size)?;
+    let dest_region = get_memory_region(&mut state.memory, dest_index, size)?;
+
+    if let (Some(src), Some(dest)) = (src_region, dest_region) {
+        // The regions may overlap, so copy as if through an intermediate buffer.
+        state.memory.copy_within(src.offset..src.offset + src.size.get(), dest.offset);
+    }
+
+    Ok(())
+}
+
 #[inline]
 pub fn msize(
     state: &mut ExecutionState,
@@ -495,6 +515,114 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_mcopy_basic() {
+        evm_unit_test! {
+            (m) {
+                PUSH1; // size
+                0x20;
+                PUSH0; // source
+                PUSH1; // destination
+                0x20;
+                MCOPY;
+            }
+            m.state.memory.grow(32);
+            m.state.memory[..32].copy_from_slice(&U256::MAX.to_bytes());
+
+            for _ in 0..4 {
+                m.step().expect("execution step failed");
+            }
+
+            assert_eq!(m.state.stack.len(), 0);
+            assert_eq!(m.state.memory.len(), 64);
+            assert_eq!(&m.state.memory[32..64], &[0xff; 32]);
+            // source is left unchanged
+            assert_eq!(&m.state.memory[..32], &[0xff; 32]);
+        };
+    }
+
+    #[test]
+    fn test_mcopy_overlap() {
+        for (src, dest) in [(0u8, 8u8), (8, 0)] {
+            evm_unit_test! {
+                (m) {
+                    PUSH1; // size
+                    0x10;
+                    PUSH1; // source
+                    {src};
+                    PUSH1; // destination
+                    {dest};
+                    MCOPY;
+                }
+                m.state.memory.grow(32);
+                for i in 0..32 {
+                    m.state.memory[i] = i as u8;
+                }
+
+                for _ in 0..4 {
+                    m.step().expect("execution step failed");
+                }
+
+                let expected: Vec<u8> = (src..src + 0x10).collect();
+                let dest = dest as usize;
+                assert_eq!(&m.state.memory[dest..dest + 0x10], &expected[..]);
+            };
+        }
+    }
+
+    #[test]
+    fn test_mcopy_expands_memory() {
+        // expanded to cover the source region, which reads as zeroes
+        evm_unit_test! {
+            (m) {
+                PUSH1; // size
+                0x20;
+                PUSH1; // source
+                0x40;
+                PUSH0; // destination
+                MCOPY;
+                MSIZE;
+            }
+            m.state.memory.grow(32);
+            m.state.memory[..32].copy_from_slice(&[0xff; 32]);
+
+            for _ in 0..5 {
+                m.step().expect("execution step failed");
+            }
+
+            assert_eq!(m.state.stack.pop().unwrap(), U256::from(96));
+            assert_eq!(&m.state.memory[..32], &[0; 32]);
+        };
+    }
+
+    #[test]
+    fn test_mcopy_zero_size() {
+        // no expansion for empty copies, however large the offsets
+        evm_unit_test! {
+            (m) {
+                PUSH0; // size
+                PUSH4; // source
+                0xff;
+                0xff;
+                0xff;
+                0xff;
+                PUSH4; // destination
+                0xff;
+                0xff;
+                0xff;
+                0xff;
+                MCOPY;
+                MSIZE;
+            }
+
+            for _ in 0..5 {
+                m.step().expect("execution step failed");
+            }
+
+            assert_eq!(m.state.stack.pop().unwrap(), U256::zero());
+        };
+    }
+
     macro_rules! check_mem {
         ($mem:ident, $region:ident, $len:expr) => {
             match $region {
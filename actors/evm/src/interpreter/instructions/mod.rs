@@ -336,8 +336,11 @@ def_stdfun! { SELFBALANCE() => state::selfbalance }
 def_stdfun! { MLOAD(a) => memory::mload }
 def_stdproc! { MSTORE(a, b) => memory::mstore }
 def_stdproc! { MSTORE8(a, b) => memory::mstore8 }
+def_stdproc! { MCOPY(a, b, c) => memory::mcopy }
 def_stdfun! { SLOAD(a) => storage::sload }
 def_stdproc! { SSTORE(a, b) => storage::sstore }
+def_stdfun! { TLOAD(a) => storage::tload }
+def_stdproc! { TSTORE(a, b) => storage::tstore }
 def_stdfun! { MSIZE() => memory::msize }
 def_stdfun! { GAS() => context::gas }
 def_stdlog! { LOG0(0, ()) }
@@ -30,6 +30,29 @@ pub fn sstore(
     system.set_storage(key, value)
 }
 
+#[inline]
+pub fn tload(
+    _state: &mut ExecutionState,
+    system: &mut System<impl Runtime>,
+    location: U256,
+) -> Result<U256, ActorError> {
+    system.get_transient_storage(location)
+}
+
+#[inline]
+pub fn tstore(
+    _state: &mut ExecutionState,
+    system: &mut System<impl Runtime>,
+    key: U256,
+    value: U256,
+) -> Result<(), ActorError> {
+    if system.readonly {
+        return Err(ActorError::read_only("transient store called while read-only".into()));
+    }
+
+    system.set_transient_storage(key, value)
+}
+
 #[cfg(test)]
 mod tests {
     use fil_actors_evm_shared::uints::U256;
@@ -82,4 +105,80 @@ mod tests {
             assert_eq!(m.system.get_storage(U256::from(0)).unwrap(), U256::from(0x42));
         };
     }
+
+    #[test]
+    fn test_sstore_write_back() {
+        evm_unit_test! {
+            (rt) {}
+            (m) {
+                SSTORE;
+                SSTORE;
+                SSTORE;
+                SSTORE;
+            }
+            // Slot 0 is written twice, and slot 1 is written then cleared.
+            for (key, value) in [(1, 0), (1, 7), (0, 2), (0, 1)] {
+                m.state.stack.push(U256::from(value)).unwrap();
+                m.state.stack.push(U256::from(key)).unwrap();
+            }
+            for _ in 0..4 {
+                m.step().expect("execution step failed");
+            }
+            assert_eq!(m.system.get_storage(U256::from(0)).unwrap(), U256::from(2));
+            m.system.flush().unwrap();
+
+            let mut system = System::load(&rt).unwrap();
+            assert_eq!(system.get_storage(U256::from(0)).unwrap(), U256::from(2));
+            assert_eq!(system.get_storage(U256::from(1)).unwrap(), U256::from(0));
+        };
+    }
+
+    #[test]
+    fn test_tload() {
+        evm_unit_test! {
+            (m) {
+                TLOAD;
+            }
+            m.system.set_transient_storage(U256::from(0), U256::from(0x42)).unwrap();
+            m.state.stack.push(U256::from(0)).unwrap();
+            let result = m.step();
+            assert!(result.is_ok(), "execution step failed");
+            assert_eq!(m.state.stack.len(), 1);
+            assert_eq!(m.state.stack.pop().unwrap(), U256::from(0x42));
+            // transient and persistent storage are separate
+            assert_eq!(m.system.get_storage(U256::from(0)).unwrap(), U256::from(0));
+        };
+    }
+
+    #[test]
+    fn test_tstore() {
+        evm_unit_test! {
+            (m) {
+                TSTORE;
+            }
+
+            m.state.stack.push(U256::from(0x42)).unwrap();
+            m.state.stack.push(U256::from(0)).unwrap();
+            let result = m.step();
+            assert!(result.is_ok(), "execution step failed");
+            assert_eq!(m.state.stack.len(), 0);
+            assert_eq!(m.system.get_transient_storage(U256::from(0)).unwrap(), U256::from(0x42));
+            assert_eq!(m.system.get_storage(U256::from(0)).unwrap(), U256::from(0));
+        };
+    }
+
+    #[test]
+    fn test_tstore_readonly() {
+        evm_unit_test! {
+            (m) {
+                TSTORE;
+            }
+
+            m.system.readonly = true;
+            m.state.stack.push(U256::from(0x42)).unwrap();
+            m.state.stack.push(U256::from(0)).unwrap();
+            let result = m.step();
+            assert_eq!(result.unwrap_err().exit_code(), fvm_shared::error::ExitCode::USR_READ_ONLY);
+        };
+    }
 }
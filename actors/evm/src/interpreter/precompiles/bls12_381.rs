@@ -0,0 +1,510 @@
+//! BLS12-381 precompiles, as specified by [EIP-2537](https://eips.ethereum.org/EIPS/eip-2537).
+//!
+//! Field elements are encoded as 64 byte big-endian integers, whose top 16 bytes must be zero and
+//! whose value must be less than the field modulus. Points are encoded as their affine `(x, y)`
+//! coordinates, with the point at infinity encoded as all zeros. Unlike the bn254 precompiles,
+//! inputs are never padded, so all input lengths are checked exactly.
+
+use blst::{
+    blst_bendian_from_fp, blst_final_exp, blst_fp, blst_fp12, blst_fp12_is_one, blst_fp12_mul,
+    blst_fp12_one, blst_fp2, blst_fp_from_bendian, blst_map_to_g1, blst_map_to_g2,
+    blst_miller_loop, blst_p1, blst_p1_add_or_double, blst_p1_affine, blst_p1_affine_in_g1,
+    blst_p1_affine_is_inf, blst_p1_affine_on_curve, blst_p1_from_affine, blst_p1_is_inf,
+    blst_p1_mult, blst_p1_to_affine, blst_p2, blst_p2_add_or_double, blst_p2_affine,
+    blst_p2_affine_in_g2, blst_p2_affine_is_inf, blst_p2_affine_on_curve, blst_p2_from_affine,
+    blst_p2_is_inf, blst_p2_mult, blst_p2_to_affine,
+};
+use fil_actors_evm_shared::uints::U256;
+use fil_actors_runtime::runtime::Runtime;
+
+use crate::interpreter::{precompiles::PrecompileError, System};
+use crate::EVM_WORD_SIZE;
+
+use super::{PrecompileContext, PrecompileResult};
+
+/// Length of an encoded base field element, including padding.
+const FP_LEN: usize = 64;
+/// Length of a base field element without padding.
+const FP_UNPADDED_LEN: usize = 48;
+const FP_PAD_LEN: usize = FP_LEN - FP_UNPADDED_LEN;
+const FP2_LEN: usize = 2 * FP_LEN;
+const G1_LEN: usize = 2 * FP_LEN;
+const G2_LEN: usize = 2 * FP2_LEN;
+const SCALAR_LEN: usize = 32;
+
+/// The base field modulus, big-endian.
+const MODULUS: [u8; FP_UNPADDED_LEN] = hex_literal::hex!(
+    "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab"
+);
+
+/// Decodes a padded base field element, rejecting non-canonical encodings.
+fn read_fp(input: &[u8]) -> Result<blst_fp, PrecompileError> {
+    debug_assert_eq!(input.len(), FP_LEN);
+    let (padding, value) = input.split_at(FP_PAD_LEN);
+    // Big-endian byte arrays of the same length compare as the integers they encode.
+    if padding.iter().any(|b| *b != 0) || value >= &MODULUS[..] {
+        return Err(PrecompileError::InvalidInput);
+    }
+    let mut fp = blst_fp::default();
+    unsafe { blst_fp_from_bendian(&mut fp, value.as_ptr()) };
+    Ok(fp)
+}
+
+fn read_fp2(input: &[u8]) -> Result<blst_fp2, PrecompileError> {
+    debug_assert_eq!(input.len(), FP2_LEN);
+    let (c0, c1) = input.split_at(FP_LEN);
+    Ok(blst_fp2 { fp: [read_fp(c0)?, read_fp(c1)?] })
+}
+
+fn write_fp(fp: &blst_fp, out: &mut Vec<u8>) {
+    let mut value = [0u8; FP_UNPADDED_LEN];
+    unsafe { blst_bendian_from_fp(value.as_mut_ptr(), fp) };
+    out.extend_from_slice(&[0u8; FP_PAD_LEN]);
+    out.extend_from_slice(&value);
+}
+
+/// Decodes a G1 point, checking that it's on the curve and, optionally, in the subgroup.
+fn read_g1(input: &[u8], subgroup_check: bool) -> Result<blst_p1_affine, PrecompileError> {
+    debug_assert_eq!(input.len(), G1_LEN);
+    // blst also represents the point at infinity as all zeros.
+    if input.iter().all(|b| *b == 0) {
+        return Ok(blst_p1_affine::default());
+    }
+    let (x, y) = input.split_at(FP_LEN);
+    let point = blst_p1_affine { x: read_fp(x)?, y: read_fp(y)? };
+    if !unsafe { blst_p1_affine_on_curve(&point) } {
+        return Err(PrecompileError::InvalidInput);
+    }
+    if subgroup_check && !unsafe { blst_p1_affine_in_g1(&point) } {
+        return Err(PrecompileError::InvalidInput);
+    }
+    Ok(point)
+}
+
+/// Decodes a G2 point, checking that it's on the curve and, optionally, in the subgroup.
+fn read_g2(input: &[u8], subgroup_check: bool) -> Result<blst_p2_affine, PrecompileError> {
+    debug_assert_eq!(input.len(), G2_LEN);
+    if input.iter().all(|b| *b == 0) {
+        return Ok(blst_p2_affine::default());
+    }
+    let (x, y) = input.split_at(FP2_LEN);
+    let point = blst_p2_affine { x: read_fp2(x)?, y: read_fp2(y)? };
+    if !unsafe { blst_p2_affine_on_curve(&point) } {
+        return Err(PrecompileError::InvalidInput);
+    }
+    if subgroup_check && !unsafe { blst_p2_affine_in_g2(&point) } {
+        return Err(PrecompileError::InvalidInput);
+    }
+    Ok(point)
+}
+
+fn encode_g1(point: &blst_p1) -> Vec<u8> {
+    if unsafe { blst_p1_is_inf(point) } {
+        return vec![0u8; G1_LEN];
+    }
+    let mut affine = blst_p1_affine::default();
+    unsafe { blst_p1_to_affine(&mut affine, point) };
+    let mut out = Vec::with_capacity(G1_LEN);
+    write_fp(&affine.x, &mut out);
+    write_fp(&affine.y, &mut out);
+    out
+}
+
+fn encode_g2(point: &blst_p2) -> Vec<u8> {
+    if unsafe { blst_p2_is_inf(point) } {
+        return vec![0u8; G2_LEN];
+    }
+    let mut affine = blst_p2_affine::default();
+    unsafe { blst_p2_to_affine(&mut affine, point) };
+    let mut out = Vec::with_capacity(G2_LEN);
+    for fp in affine.x.fp.iter().chain(affine.y.fp.iter()) {
+        write_fp(fp, &mut out);
+    }
+    out
+}
+
+/// Converts a big-endian scalar to the little-endian form blst multiplies by.
+/// Scalars aren't required to be less than the group order.
+fn read_scalar(input: &[u8]) -> [u8; SCALAR_LEN] {
+    debug_assert_eq!(input.len(), SCALAR_LEN);
+    let mut scalar = [0u8; SCALAR_LEN];
+    scalar.copy_from_slice(input);
+    scalar.reverse();
+    scalar
+}
+
+fn g1_from_affine(point: &blst_p1_affine) -> blst_p1 {
+    let mut out = blst_p1::default();
+    unsafe { blst_p1_from_affine(&mut out, point) };
+    out
+}
+
+fn g2_from_affine(point: &blst_p2_affine) -> blst_p2 {
+    let mut out = blst_p2::default();
+    unsafe { blst_p2_from_affine(&mut out, point) };
+    out
+}
+
+/// Returns whether the product of the pairings of each (G1, G2) pair is the identity.
+/// The points must already have been checked to be in their subgroups.
+pub(super) fn pairing_is_one(pairs: &[(blst_p1_affine, blst_p2_affine)]) -> bool {
+    let mut acc = unsafe { *blst_fp12_one() };
+    for (p, q) in pairs {
+        if unsafe { blst_p1_affine_is_inf(p) || blst_p2_affine_is_inf(q) } {
+            // Pairings with the point at infinity are the identity.
+            continue;
+        }
+
+        let mut paired = blst_fp12::default();
+        unsafe {
+            blst_miller_loop(&mut paired, q, p);
+            // blst allows the output to alias an input.
+            let acc: *mut blst_fp12 = &mut acc;
+            blst_fp12_mul(acc, acc, &paired);
+        }
+    }
+
+    let mut result = blst_fp12::default();
+    unsafe {
+        blst_final_exp(&mut result, &acc);
+        blst_fp12_is_one(&result)
+    }
+}
+
+/// add two points in G1
+pub(super) fn bls12_g1_add<RT: Runtime>(
+    _: &mut System<RT>,
+    input: &[u8],
+    _: PrecompileContext,
+) -> PrecompileResult {
+    if input.len() != 2 * G1_LEN {
+        return Err(PrecompileError::IncorrectInputSize);
+    }
+    let a = g1_from_affine(&read_g1(&input[..G1_LEN], false)?);
+    let b = g1_from_affine(&read_g1(&input[G1_LEN..], false)?);
+
+    let mut sum = blst_p1::default();
+    unsafe { blst_p1_add_or_double(&mut sum, &a, &b) };
+    Ok(encode_g1(&sum))
+}
+
+/// multi-scalar multiplication in G1
+pub(super) fn bls12_g1_msm<RT: Runtime>(
+    _: &mut System<RT>,
+    input: &[u8],
+    _: PrecompileContext,
+) -> PrecompileResult {
+    const PAIR_LEN: usize = G1_LEN + SCALAR_LEN;
+    if input.is_empty() || input.len() % PAIR_LEN != 0 {
+        return Err(PrecompileError::IncorrectInputSize);
+    }
+
+    let mut acc = blst_p1::default();
+    for pair in input.chunks_exact(PAIR_LEN) {
+        let point = g1_from_affine(&read_g1(&pair[..G1_LEN], true)?);
+        let scalar = read_scalar(&pair[G1_LEN..]);
+
+        let mut product = blst_p1::default();
+        unsafe {
+            blst_p1_mult(&mut product, &point, scalar.as_ptr(), SCALAR_LEN * 8);
+            let acc: *mut blst_p1 = &mut acc;
+            blst_p1_add_or_double(acc, acc, &product);
+        }
+    }
+    Ok(encode_g1(&acc))
+}
+
+/// add two points in G2
+pub(super) fn bls12_g2_add<RT: Runtime>(
+    _: &mut System<RT>,
+    input: &[u8],
+    _: PrecompileContext,
+) -> PrecompileResult {
+    if input.len() != 2 * G2_LEN {
+        return Err(PrecompileError::IncorrectInputSize);
+    }
+    let a = g2_from_affine(&read_g2(&input[..G2_LEN], false)?);
+    let b = g2_from_affine(&read_g2(&input[G2_LEN..], false)?);
+
+    let mut sum = blst_p2::default();
+    unsafe { blst_p2_add_or_double(&mut sum, &a, &b) };
+    Ok(encode_g2(&sum))
+}
+
+/// multi-scalar multiplication in G2
+pub(super) fn bls12_g2_msm<RT: Runtime>(
+    _: &mut System<RT>,
+    input: &[u8],
+    _: PrecompileContext,
+) -> PrecompileResult {
+    const PAIR_LEN: usize = G2_LEN + SCALAR_LEN;
+    if input.is_empty() || input.len() % PAIR_LEN != 0 {
+        return Err(PrecompileError::IncorrectInputSize);
+    }
+
+    let mut acc = blst_p2::default();
+    for pair in input.chunks_exact(PAIR_LEN) {
+        let point = g2_from_affine(&read_g2(&pair[..G2_LEN], true)?);
+        let scalar = read_scalar(&pair[G2_LEN..]);
+
+        let mut product = blst_p2::default();
+        unsafe {
+            blst_p2_mult(&mut product, &point, scalar.as_ptr(), SCALAR_LEN * 8);
+            let acc: *mut blst_p2 = &mut acc;
+            blst_p2_add_or_double(acc, acc, &product);
+        }
+    }
+    Ok(encode_g2(&acc))
+}
+
+/// checks that the product of pairings of (G1, G2) pairs is the identity
+pub(super) fn bls12_pairing_check<RT: Runtime>(
+    _: &mut System<RT>,
+    input: &[u8],
+    _: PrecompileContext,
+) -> PrecompileResult {
+    const PAIR_LEN: usize = G1_LEN + G2_LEN;
+    if input.is_empty() || input.len() % PAIR_LEN != 0 {
+        return Err(PrecompileError::IncorrectInputSize);
+    }
+
+    let mut pairs = Vec::with_capacity(input.len() / PAIR_LEN);
+    for pair in input.chunks_exact(PAIR_LEN) {
+        // Both points are validated even if the other is at infinity.
+        pairs.push((read_g1(&pair[..G1_LEN], true)?, read_g2(&pair[G1_LEN..], true)?));
+    }
+
+    let success = if pairing_is_one(&pairs) { U256::ONE } else { U256::ZERO };
+    let mut ret = [0u8; EVM_WORD_SIZE];
+    success.to_big_endian(&mut ret);
+    Ok(ret.to_vec())
+}
+
+/// maps a base field element to a point in G1
+pub(super) fn bls12_map_fp_to_g1<RT: Runtime>(
+    _: &mut System<RT>,
+    input: &[u8],
+    _: PrecompileContext,
+) -> PrecompileResult {
+    if input.len() != FP_LEN {
+        return Err(PrecompileError::IncorrectInputSize);
+    }
+    let fp = read_fp(input)?;
+
+    // Maps with the simplified SWU map and clears the cofactor, as in the hash-to-curve spec.
+    let mut point = blst_p1::default();
+    unsafe { blst_map_to_g1(&mut point, &fp, std::ptr::null()) };
+    Ok(encode_g1(&point))
+}
+
+/// maps an extension field element to a point in G2
+pub(super) fn bls12_map_fp2_to_g2<RT: Runtime>(
+    _: &mut System<RT>,
+    input: &[u8],
+    _: PrecompileContext,
+) -> PrecompileResult {
+    if input.len() != FP2_LEN {
+        return Err(PrecompileError::IncorrectInputSize);
+    }
+    let fp2 = read_fp2(input)?;
+
+    let mut point = blst_p2::default();
+    unsafe { blst_map_to_g2(&mut point, &fp2, std::ptr::null()) };
+    Ok(encode_g2(&point))
+}
+
+#[cfg(test)]
+mod tests {
+    use fil_actors_runtime::test_utils::MockRuntime;
+    use hex_literal::hex;
+
+    use super::*;
+
+    const G1: [u8; G1_LEN] = hex!(
+        "0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"
+        "0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1"
+    );
+    const G1_NEG: [u8; G1_LEN] = hex!(
+        "0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"
+        "00000000000000000000000000000000114d1d6855d545a8aa7d76c8cf2e21f267816aef1db507c96655b9d5caac42364e6f38ba0ecb751bad54dcd6b939c2ca"
+    );
+    const G1_DOUBLE: [u8; G1_LEN] = hex!(
+        "000000000000000000000000000000000572cbea904d67468808c8eb50a9450c9721db309128012543902d0ac358a62ae28f75bb8f1c7c42c39a8c5529bf0f4e"
+        "00000000000000000000000000000000166a9d8cabc673a322fda673779d8e3822ba3ecb8670e461f73bb9021d5fd76a4c56d9d4cd16bd1bba86881979749d28"
+    );
+    const G1_TIMES_8: [u8; G1_LEN] = hex!(
+        "00000000000000000000000000000000085ae765588126f5e860d019c0e26235f567a9c0c0b2d8ff30f3e8d436b1082596e5e7462d20f5be3764fd473e57f9cf"
+        "0000000000000000000000000000000019e7dfab8a794b6abb9f84e57739de172a63415273f460d1607fa6a74f0acd97d9671b801dd1fd4f18232dd1259359a1"
+    );
+    /// On the curve, but not in the prime order subgroup.
+    const G1_NOT_IN_SUBGROUP: [u8; G1_LEN] = hex!(
+        "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004"
+        "000000000000000000000000000000000a989badd40d6212b33cffc3f3763e9bc760f988c9926b26da9dd85e928483446346b8ed00e1de5d5ea93e354abe706c"
+    );
+
+    const G2: [u8; G2_LEN] = hex!(
+        "00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8"
+        "0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e"
+        "000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801"
+        "000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be"
+    );
+    const G2_DOUBLE: [u8; G2_LEN] = hex!(
+        "000000000000000000000000000000001638533957d540a9d2370f17cc7ed5863bc0b995b8825e0ee1ea1e1e4d00dbae81f14b0bf3611b78c952aacab827a053"
+        "000000000000000000000000000000000a4edef9c1ed7f729f520e47730a124fd70662a904ba1074728114d1031e1572c6c886f6b57ec72a6178288c47c33577"
+        "000000000000000000000000000000000468fb440d82b0630aeb8dca2b5256789a66da69bf91009cbfe6bd221e47aa8ae88dece9764bf3bd999d95d71e4c9899"
+        "000000000000000000000000000000000f6d4552fa65dd2638b361543f887136a43253d9c66c411697003f7a13c308f5422e1aa0a59c8967acdefd8b6e36ccf3"
+    );
+    const G2_TIMES_5: [u8; G2_LEN] = hex!(
+        "000000000000000000000000000000000411a5de6730ffece671a9f21d65028cc0f1102378de124562cb1ff49db6f004fcd14d683024b0548eff3d1468df2688"
+        "0000000000000000000000000000000000fb837804dba8213329db46608b6c121d973363c1234a86dd183baff112709cf97096c5e9a1a770ee9d7dc641a894d6"
+        "0000000000000000000000000000000019b5e8f5d4a72f2b75811ac084a7f814317360bac52f6aab15eed416b4ef9938e0bdc4865cc2c4d0fd947e7c6925fd14"
+        "00000000000000000000000000000000093567b4228be17ee62d11a254edd041ee4b953bffb8b8c7f925bd6662b4298bac2822b446f5b5de3b893e1be5aa4986"
+    );
+
+    fn scalar(n: u64) -> [u8; SCALAR_LEN] {
+        let mut out = [0u8; SCALAR_LEN];
+        U256::from(n).to_big_endian(&mut out);
+        out
+    }
+
+    #[test]
+    fn g1_add() {
+        let rt = MockRuntime::default();
+        let mut system = System::create(&rt).unwrap();
+
+        let res = bls12_g1_add(&mut system, &[G1, G1].concat(), PrecompileContext::default());
+        assert_eq!(res.unwrap(), G1_DOUBLE);
+
+        let res = bls12_g1_add(&mut system, &[G1, G1_NEG].concat(), PrecompileContext::default());
+        assert_eq!(res.unwrap(), [0u8; G1_LEN]);
+
+        let input = [G1, [0u8; G1_LEN]].concat();
+        let res = bls12_g1_add(&mut system, &input, PrecompileContext::default());
+        assert_eq!(res.unwrap(), G1);
+
+        // no subgroup check for addition
+        let input = [G1_NOT_IN_SUBGROUP, [0u8; G1_LEN]].concat();
+        let res = bls12_g1_add(&mut system, &input, PrecompileContext::default());
+        assert_eq!(res.unwrap(), G1_NOT_IN_SUBGROUP);
+
+        // point not on curve
+        let mut not_on_curve = G1;
+        not_on_curve[G1_LEN - 1] ^= 1;
+        let input = [G1, not_on_curve].concat();
+        let res = bls12_g1_add(&mut system, &input, PrecompileContext::default());
+        assert!(matches!(res, Err(PrecompileError::InvalidInput)));
+
+        // non-zero padding
+        let mut bad_padding = G1;
+        bad_padding[0] = 1;
+        let input = [G1, bad_padding].concat();
+        let res = bls12_g1_add(&mut system, &input, PrecompileContext::default());
+        assert!(matches!(res, Err(PrecompileError::InvalidInput)));
+
+        // inputs aren't padded
+        let res = bls12_g1_add(&mut system, &G1, PrecompileContext::default());
+        assert!(matches!(res, Err(PrecompileError::IncorrectInputSize)));
+    }
+
+    #[test]
+    fn g1_msm() {
+        let rt = MockRuntime::default();
+        let mut system = System::create(&rt).unwrap();
+
+        // 2 * G + 3 * 2G = 8G
+        let input = [&G1[..], &scalar(2), &G1_DOUBLE, &scalar(3)].concat();
+        let res = bls12_g1_msm(&mut system, &input, PrecompileContext::default());
+        assert_eq!(res.unwrap(), G1_TIMES_8);
+
+        let input = [&G1[..], &scalar(0)].concat();
+        let res = bls12_g1_msm(&mut system, &input, PrecompileContext::default());
+        assert_eq!(res.unwrap(), [0u8; G1_LEN]);
+
+        let input = [&G1_NOT_IN_SUBGROUP[..], &scalar(1)].concat();
+        let res = bls12_g1_msm(&mut system, &input, PrecompileContext::default());
+        assert!(matches!(res, Err(PrecompileError::InvalidInput)));
+
+        let res = bls12_g1_msm(&mut system, &[], PrecompileContext::default());
+        assert!(matches!(res, Err(PrecompileError::IncorrectInputSize)));
+    }
+
+    #[test]
+    fn g2_add_and_msm() {
+        let rt = MockRuntime::default();
+        let mut system = System::create(&rt).unwrap();
+
+        let res = bls12_g2_add(&mut system, &[G2, G2].concat(), PrecompileContext::default());
+        assert_eq!(res.unwrap(), G2_DOUBLE);
+
+        let input = [G2, [0u8; G2_LEN]].concat();
+        let res = bls12_g2_add(&mut system, &input, PrecompileContext::default());
+        assert_eq!(res.unwrap(), G2);
+
+        // G + 2 * 2G = 5G
+        let input = [&G2[..], &scalar(1), &G2_DOUBLE, &scalar(2)].concat();
+        let res = bls12_g2_msm(&mut system, &input, PrecompileContext::default());
+        assert_eq!(res.unwrap(), G2_TIMES_5);
+
+        let res = bls12_g2_msm(&mut system, &G2, PrecompileContext::default());
+        assert!(matches!(res, Err(PrecompileError::IncorrectInputSize)));
+    }
+
+    #[test]
+    fn pairing_check() {
+        let rt = MockRuntime::default();
+        let mut system = System::create(&rt).unwrap();
+
+        let mut one = [0u8; EVM_WORD_SIZE];
+        one[EVM_WORD_SIZE - 1] = 1;
+
+        // e(G1, G2) * e(-G1, G2) = 1
+        let input = [&G1[..], &G2, &G1_NEG, &G2].concat();
+        let res = bls12_pairing_check(&mut system, &input, PrecompileContext::default());
+        assert_eq!(res.unwrap(), one);
+
+        // e(2 * G1, G2) * e(-G1, 2 * G2) = 1
+        let input = [&G1_DOUBLE[..], &G2, &G1_NEG, &G2_DOUBLE].concat();
+        let res = bls12_pairing_check(&mut system, &input, PrecompileContext::default());
+        assert_eq!(res.unwrap(), one);
+
+        let input = [&G1[..], &G2].concat();
+        let res = bls12_pairing_check(&mut system, &input, PrecompileContext::default());
+        assert_eq!(res.unwrap(), [0u8; EVM_WORD_SIZE]);
+
+        let input = [&[0u8; G1_LEN][..], &G2].concat();
+        let res = bls12_pairing_check(&mut system, &input, PrecompileContext::default());
+        assert_eq!(res.unwrap(), one);
+
+        let input = [&G1_NOT_IN_SUBGROUP[..], &[0u8; G2_LEN]].concat();
+        let res = bls12_pairing_check(&mut system, &input, PrecompileContext::default());
+        assert!(matches!(res, Err(PrecompileError::InvalidInput)));
+
+        let res = bls12_pairing_check(&mut system, &[], PrecompileContext::default());
+        assert!(matches!(res, Err(PrecompileError::IncorrectInputSize)));
+    }
+
+    #[test]
+    fn map_to_curve() {
+        let rt = MockRuntime::default();
+        let mut system = System::create(&rt).unwrap();
+
+        let mut fp = [0u8; FP_LEN];
+        fp[FP_LEN - 1] = 42;
+        let res = bls12_map_fp_to_g1(&mut system, &fp, PrecompileContext::default()).unwrap();
+        // mapped points are in the subgroup
+        read_g1(&res, true).unwrap();
+
+        let fp2 = [fp, fp].concat();
+        let res = bls12_map_fp2_to_g2(&mut system, &fp2, PrecompileContext::default()).unwrap();
+        read_g2(&res, true).unwrap();
+
+        // field elements must be less than the modulus
+        let mut modulus = [0u8; FP_LEN];
+        modulus[FP_PAD_LEN..].copy_from_slice(&MODULUS);
+        let res = bls12_map_fp_to_g1(&mut system, &modulus, PrecompileContext::default());
+        assert!(matches!(res, Err(PrecompileError::InvalidInput)));
+
+        let res = bls12_map_fp2_to_g2(&mut system, &fp, PrecompileContext::default());
+        assert!(matches!(res, Err(PrecompileError::IncorrectInputSize)));
+    }
+}
@@ -0,0 +1,424 @@
+//! A native precompile exposing the exported getters of the built-in market, miner, power and
+//! verified registry actors to solidity, translating between the solidity ABI and CBOR.
+
+use fil_actors_evm_shared::uints::U256;
+use fil_actors_runtime::{
+    actor_error, deserialize_block, runtime::Runtime, STORAGE_MARKET_ACTOR_ADDR,
+    STORAGE_POWER_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
+};
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_shared::{
+    address::Address, bigint::BigInt, bigint::Sign, econ::TokenAmount, sys::SendFlags, MethodNum,
+};
+use num_traits::Zero;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::ext::{market, miner, power, verifreg};
+use crate::interpreter::System;
+use crate::reader::ValueReader;
+use crate::EVM_WORD_SIZE;
+
+use super::{PrecompileContext, PrecompileError, PrecompileResult};
+
+// Solidity function selectors, i.e. the first 4 bytes of the keccak256 hash of the signature.
+/// `getBalance(uint64)`
+const GET_BALANCE: [u8; 4] = hex_literal::hex!("341b9e5a");
+/// `getDealDataCommitment(uint64)`
+const GET_DEAL_DATA_COMMITMENT: [u8; 4] = hex_literal::hex!("75d94e9a");
+/// `getDealClient(uint64)`
+const GET_DEAL_CLIENT: [u8; 4] = hex_literal::hex!("5de4d198");
+/// `getDealProvider(uint64)`
+const GET_DEAL_PROVIDER: [u8; 4] = hex_literal::hex!("1e1ebc4e");
+/// `getDealTerm(uint64)`
+const GET_DEAL_TERM: [u8; 4] = hex_literal::hex!("1a329cbc");
+/// `getDealTotalPrice(uint64)`
+const GET_DEAL_TOTAL_PRICE: [u8; 4] = hex_literal::hex!("58b07bc9");
+/// `getDealClientCollateral(uint64)`
+const GET_DEAL_CLIENT_COLLATERAL: [u8; 4] = hex_literal::hex!("d4254e8f");
+/// `getDealProviderCollateral(uint64)`
+const GET_DEAL_PROVIDER_COLLATERAL: [u8; 4] = hex_literal::hex!("119ec8d9");
+/// `getDealVerified(uint64)`
+const GET_DEAL_VERIFIED: [u8; 4] = hex_literal::hex!("b91b14b4");
+/// `getDealActivation(uint64)`
+const GET_DEAL_ACTIVATION: [u8; 4] = hex_literal::hex!("9b921f34");
+/// `getSectorSize(uint64)`
+const GET_SECTOR_SIZE: [u8; 4] = hex_literal::hex!("32961332");
+/// `getAvailableBalance(uint64)`
+const GET_AVAILABLE_BALANCE: [u8; 4] = hex_literal::hex!("c3a5be4b");
+/// `isControllingAddress(uint64,uint64)`
+const IS_CONTROLLING_ADDRESS: [u8; 4] = hex_literal::hex!("781ec3d8");
+/// `networkRawPower()`
+const NETWORK_RAW_POWER: [u8; 4] = hex_literal::hex!("46dedc39");
+/// `minerRawPower(uint64)`
+const MINER_RAW_POWER: [u8; 4] = hex_literal::hex!("06e9f834");
+/// `minerCount()`
+const MINER_COUNT: [u8; 4] = hex_literal::hex!("6d1da431");
+/// `minerConsensusCount()`
+const MINER_CONSENSUS_COUNT: [u8; 4] = hex_literal::hex!("0698a274");
+/// `getClaim(uint64,uint64)`
+const GET_CLAIM: [u8; 4] = hex_literal::hex!("ba422358");
+
+/// A solidity ABI value returned by the precompile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word([u8; 32]),
+    Bytes(Vec<u8>),
+}
+
+impl Token {
+    fn uint(v: u64) -> Token {
+        Token::Word(U256::from(v).to_bytes())
+    }
+
+    fn int(v: i64) -> Token {
+        let mut word = if v < 0 { [0xff; 32] } else { [0; 32] };
+        word[24..].copy_from_slice(&v.to_be_bytes());
+        Token::Word(word)
+    }
+
+    fn bool(v: bool) -> Token {
+        Token::uint(v as u64)
+    }
+
+    /// Encodes a non-negative big integer as a `uint256`, failing if it doesn't fit.
+    fn big_uint(v: &BigInt) -> Result<Token, PrecompileError> {
+        let (sign, bytes) = v.to_bytes_be();
+        if sign == Sign::Minus || bytes.len() > EVM_WORD_SIZE {
+            return Err(PrecompileError::InvalidInput);
+        }
+        let mut word = [0u8; 32];
+        word[EVM_WORD_SIZE - bytes.len()..].copy_from_slice(&bytes);
+        Ok(Token::Word(word))
+    }
+
+    fn token_amount(v: &TokenAmount) -> Result<Token, PrecompileError> {
+        Token::big_uint(v.atto())
+    }
+}
+
+/// ABI encodes a tuple of values: static words inline, dynamic bytes as offsets into a tail.
+fn encode(tokens: &[Token]) -> Vec<u8> {
+    let head_len = tokens.len() * EVM_WORD_SIZE;
+    let mut head = Vec::with_capacity(head_len);
+    let mut tail = Vec::new();
+    for token in tokens {
+        match token {
+            Token::Word(word) => head.extend_from_slice(word),
+            Token::Bytes(bytes) => {
+                head.extend_from_slice(&U256::from(head_len + tail.len()).to_bytes());
+                tail.extend_from_slice(&U256::from(bytes.len()).to_bytes());
+                tail.extend_from_slice(bytes);
+                // Pad out to the next increment of 32 bytes.
+                let offset = tail.len() % EVM_WORD_SIZE;
+                if offset > 0 {
+                    tail.resize(tail.len() - offset + EVM_WORD_SIZE, 0);
+                }
+            }
+        }
+    }
+    head.extend_from_slice(&tail);
+    head
+}
+
+/// Calls a built-in actor getter with the read-only flag set, returning the decoded result.
+fn call_getter<RT: Runtime, P: Serialize, R: DeserializeOwned>(
+    system: &mut System<RT>,
+    ctx: PrecompileContext,
+    to: &Address,
+    method: MethodNum,
+    params: Option<&P>,
+) -> Result<R, PrecompileError> {
+    let params = match params {
+        Some(p) => IpldBlock::serialize_cbor(p)
+            .map_err(|e| actor_error!(serialization; "failed to serialize params: {}", e))?,
+        None => None,
+    };
+    let ret = system.send(
+        to,
+        method,
+        params,
+        TokenAmount::zero(),
+        Some(ctx.gas_limit),
+        SendFlags::READ_ONLY,
+    )?;
+    Ok(deserialize_block(ret)?)
+}
+
+/// Calls an exported getter method on a built-in actor.
+///
+/// The input is a solidity function call: a 4 byte selector followed by the ABI encoded
+/// arguments. The result is returned ABI encoded. The supported functions are:
+///
+/// ```text
+/// getBalance(uint64 account) returns (uint256 balance, uint256 locked)
+/// getDealDataCommitment(uint64 dealId) returns (bytes cid, uint64 size)
+/// getDealClient(uint64 dealId) returns (uint64)
+/// getDealProvider(uint64 dealId) returns (uint64)
+/// getDealTerm(uint64 dealId) returns (int64 start, int64 duration)
+/// getDealTotalPrice(uint64 dealId) returns (uint256)
+/// getDealClientCollateral(uint64 dealId) returns (uint256)
+/// getDealProviderCollateral(uint64 dealId) returns (uint256)
+/// getDealVerified(uint64 dealId) returns (bool)
+/// getDealActivation(uint64 dealId) returns (int64 activated, int64 terminated)
+/// getSectorSize(uint64 miner) returns (uint64)
+/// getAvailableBalance(uint64 miner) returns (uint256)
+/// isControllingAddress(uint64 miner, uint64 addr) returns (bool)
+/// networkRawPower() returns (uint256)
+/// minerRawPower(uint64 miner) returns (uint256 rawBytePower, bool meetsConsensusMinimum)
+/// minerCount() returns (int64)
+/// minerConsensusCount() returns (int64)
+/// getClaim(uint64 provider, uint64 claimId) returns (uint64 provider, uint64 client,
+///     bytes data, uint64 size, int64 termMin, int64 termMax, int64 termStart, uint64 sector)
+/// ```
+///
+/// Accounts, miners and addresses are passed as actor IDs. Reverts if the selector is unknown,
+/// the call fails, or a returned value doesn't fit its ABI type.
+pub(super) fn call_builtin_getter<RT: Runtime>(
+    system: &mut System<RT>,
+    input: &[u8],
+    ctx: PrecompileContext,
+) -> PrecompileResult {
+    if input.len() < 4 {
+        return Err(PrecompileError::IncorrectInputSize);
+    }
+    let (selector, args) = input.split_at(4);
+    let selector: [u8; 4] = selector.try_into().unwrap();
+    let mut args = ValueReader::new(args);
+
+    let tokens = match selector {
+        GET_BALANCE => {
+            let account = Address::new_id(args.read_value()?);
+            let ret: market::GetBalanceReturn = call_getter(
+                system,
+                ctx,
+                &STORAGE_MARKET_ACTOR_ADDR,
+                market::GET_BALANCE_METHOD,
+                Some(&account),
+            )?;
+            vec![Token::token_amount(&ret.balance)?, Token::token_amount(&ret.locked)?]
+        }
+        GET_DEAL_DATA_COMMITMENT => {
+            let deal_id: u64 = args.read_value()?;
+            let ret: market::GetDealDataCommitmentReturn = call_getter(
+                system,
+                ctx,
+                &STORAGE_MARKET_ACTOR_ADDR,
+                market::GET_DEAL_DATA_COMMITMENT_METHOD,
+                Some(&deal_id),
+            )?;
+            vec![Token::Bytes(ret.data.to_bytes()), Token::uint(ret.size.0)]
+        }
+        GET_DEAL_CLIENT | GET_DEAL_PROVIDER => {
+            let deal_id: u64 = args.read_value()?;
+            let method = if selector == GET_DEAL_CLIENT {
+                market::GET_DEAL_CLIENT_METHOD
+            } else {
+                market::GET_DEAL_PROVIDER_METHOD
+            };
+            let ret: market::GetDealPartyReturn =
+                call_getter(system, ctx, &STORAGE_MARKET_ACTOR_ADDR, method, Some(&deal_id))?;
+            vec![Token::uint(ret.id)]
+        }
+        GET_DEAL_TERM => {
+            let deal_id: u64 = args.read_value()?;
+            let ret: market::GetDealTermReturn = call_getter(
+                system,
+                ctx,
+                &STORAGE_MARKET_ACTOR_ADDR,
+                market::GET_DEAL_TERM_METHOD,
+                Some(&deal_id),
+            )?;
+            vec![Token::int(ret.start), Token::int(ret.duration)]
+        }
+        GET_DEAL_TOTAL_PRICE | GET_DEAL_CLIENT_COLLATERAL | GET_DEAL_PROVIDER_COLLATERAL => {
+            let deal_id: u64 = args.read_value()?;
+            let method = match selector {
+                GET_DEAL_TOTAL_PRICE => market::GET_DEAL_TOTAL_PRICE_METHOD,
+                GET_DEAL_CLIENT_COLLATERAL => market::GET_DEAL_CLIENT_COLLATERAL_METHOD,
+                _ => market::GET_DEAL_PROVIDER_COLLATERAL_METHOD,
+            };
+            let ret: market::GetDealAmountReturn =
+                call_getter(system, ctx, &STORAGE_MARKET_ACTOR_ADDR, method, Some(&deal_id))?;
+            vec![Token::token_amount(&ret.amount)?]
+        }
+        GET_DEAL_VERIFIED => {
+            let deal_id: u64 = args.read_value()?;
+            let ret: market::GetDealVerifiedReturn = call_getter(
+                system,
+                ctx,
+                &STORAGE_MARKET_ACTOR_ADDR,
+                market::GET_DEAL_VERIFIED_METHOD,
+                Some(&deal_id),
+            )?;
+            vec![Token::bool(ret.verified)]
+        }
+        GET_DEAL_ACTIVATION => {
+            let deal_id: u64 = args.read_value()?;
+            let ret: market::GetDealActivationReturn = call_getter(
+                system,
+                ctx,
+                &STORAGE_MARKET_ACTOR_ADDR,
+                market::GET_DEAL_ACTIVATION_METHOD,
+                Some(&deal_id),
+            )?;
+            vec![Token::int(ret.activated), Token::int(ret.terminated)]
+        }
+        GET_SECTOR_SIZE => {
+            let miner = Address::new_id(args.read_value()?);
+            let ret: miner::GetSectorSizeReturn =
+                call_getter::<_, (), _>(system, ctx, &miner, miner::GET_SECTOR_SIZE_METHOD, None)?;
+            vec![Token::uint(ret.sector_size)]
+        }
+        GET_AVAILABLE_BALANCE => {
+            let miner = Address::new_id(args.read_value()?);
+            let ret: miner::GetAvailableBalanceReturn = call_getter::<_, (), _>(
+                system,
+                ctx,
+                &miner,
+                miner::GET_AVAILABLE_BALANCE_METHOD,
+                None,
+            )?;
+            vec![Token::token_amount(&ret.available_balance)?]
+        }
+        IS_CONTROLLING_ADDRESS => {
+            let miner = Address::new_id(args.read_value()?);
+            let addr = Address::new_id(args.read_value()?);
+            let ret: miner::IsControllingAddressReturn = call_getter(
+                system,
+                ctx,
+                &miner,
+                miner::IS_CONTROLLING_ADDRESS_METHOD,
+                Some(&addr),
+            )?;
+            vec![Token::bool(ret.is_controlling)]
+        }
+        NETWORK_RAW_POWER => {
+            let ret: power::NetworkRawPowerReturn = call_getter::<_, (), _>(
+                system,
+                ctx,
+                &STORAGE_POWER_ACTOR_ADDR,
+                power::NETWORK_RAW_POWER_METHOD,
+                None,
+            )?;
+            vec![Token::big_uint(&ret.raw_byte_power)?]
+        }
+        MINER_RAW_POWER => {
+            let miner: u64 = args.read_value()?;
+            let ret: power::MinerRawPowerReturn = call_getter(
+                system,
+                ctx,
+                &STORAGE_POWER_ACTOR_ADDR,
+                power::MINER_RAW_POWER_METHOD,
+                Some(&miner),
+            )?;
+            vec![Token::big_uint(&ret.raw_byte_power)?, Token::bool(ret.meets_consensus_minimum)]
+        }
+        MINER_COUNT | MINER_CONSENSUS_COUNT => {
+            let method = if selector == MINER_COUNT {
+                power::MINER_COUNT_METHOD
+            } else {
+                power::MINER_CONSENSUS_COUNT_METHOD
+            };
+            let ret: power::MinerCountReturn =
+                call_getter::<_, (), _>(system, ctx, &STORAGE_POWER_ACTOR_ADDR, method, None)?;
+            vec![Token::int(ret.count)]
+        }
+        GET_CLAIM => {
+            let provider: u64 = args.read_value()?;
+            let claim_id: u64 = args.read_value()?;
+            let params = verifreg::GetClaimsParams { provider, claim_ids: vec![claim_id] };
+            let ret: verifreg::GetClaimsReturn = call_getter(
+                system,
+                ctx,
+                &VERIFIED_REGISTRY_ACTOR_ADDR,
+                verifreg::GET_CLAIMS_METHOD,
+                Some(&params),
+            )?;
+            let claim = match (ret.batch_info.success_count, ret.claims.as_slice()) {
+                (1, [claim]) => claim,
+                _ => return Err(PrecompileError::InvalidInput),
+            };
+            vec![
+                Token::uint(claim.provider),
+                Token::uint(claim.client),
+                Token::Bytes(claim.data.to_bytes()),
+                Token::uint(claim.size.0),
+                Token::int(claim.term_min),
+                Token::int(claim.term_max),
+                Token::int(claim.term_start),
+                Token::uint(claim.sector),
+            ]
+        }
+        _ => return Err(PrecompileError::InvalidInput),
+    };
+
+    Ok(encode(&tokens))
+}
+
+#[cfg(test)]
+mod tests {
+    use fvm_shared::bigint::BigInt;
+    use multihash::{Code, MultihashDigest};
+
+    use super::*;
+
+    #[test]
+    fn selectors_match_signatures() {
+        let selectors = [
+            (GET_BALANCE, "getBalance(uint64)"),
+            (GET_DEAL_DATA_COMMITMENT, "getDealDataCommitment(uint64)"),
+            (GET_DEAL_CLIENT, "getDealClient(uint64)"),
+            (GET_DEAL_PROVIDER, "getDealProvider(uint64)"),
+            (GET_DEAL_TERM, "getDealTerm(uint64)"),
+            (GET_DEAL_TOTAL_PRICE, "getDealTotalPrice(uint64)"),
+            (GET_DEAL_CLIENT_COLLATERAL, "getDealClientCollateral(uint64)"),
+            (GET_DEAL_PROVIDER_COLLATERAL, "getDealProviderCollateral(uint64)"),
+            (GET_DEAL_VERIFIED, "getDealVerified(uint64)"),
+            (GET_DEAL_ACTIVATION, "getDealActivation(uint64)"),
+            (GET_SECTOR_SIZE, "getSectorSize(uint64)"),
+            (GET_AVAILABLE_BALANCE, "getAvailableBalance(uint64)"),
+            (IS_CONTROLLING_ADDRESS, "isControllingAddress(uint64,uint64)"),
+            (NETWORK_RAW_POWER, "networkRawPower()"),
+            (MINER_RAW_POWER, "minerRawPower(uint64)"),
+            (MINER_COUNT, "minerCount()"),
+            (MINER_CONSENSUS_COUNT, "minerConsensusCount()"),
+            (GET_CLAIM, "getClaim(uint64,uint64)"),
+        ];
+        for (selector, signature) in selectors {
+            let hash = Code::Keccak256.digest(signature.as_bytes());
+            assert_eq!(selector, hash.digest()[..4], "selector of {}", signature);
+        }
+    }
+
+    #[test]
+    fn encode_static() {
+        let out = encode(&[Token::uint(5), Token::int(-2), Token::bool(true)]);
+        let mut expected = [0u8; 96];
+        expected[31] = 5;
+        expected[32..63].fill(0xff);
+        expected[63] = 0xfe;
+        expected[95] = 1;
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn encode_dynamic() {
+        let out = encode(&[Token::Bytes(vec![0xaa; 33]), Token::uint(7)]);
+        assert_eq!(out.len(), 32 * 5);
+        // Offset of the bytes, past the two head words.
+        assert_eq!(out[31], 64);
+        assert_eq!(out[63], 7);
+        // Length, then the data padded to a word boundary.
+        assert_eq!(out[95], 33);
+        assert_eq!(&out[96..129], &[0xaa; 33]);
+        assert_eq!(&out[129..], &[0u8; 31]);
+    }
+
+    #[test]
+    fn encode_big_uint() {
+        assert_eq!(Token::big_uint(&BigInt::from(0x1234)).unwrap(), Token::uint(0x1234));
+        assert!(Token::big_uint(&BigInt::from(-1)).is_err());
+        assert!(Token::big_uint(&(BigInt::from(1) << 256)).is_err());
+        assert!(Token::big_uint(&((BigInt::from(1) << 256) - 1)).is_ok());
+    }
+}
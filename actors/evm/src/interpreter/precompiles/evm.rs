@@ -442,7 +442,7 @@ mod tests {
             precompiles::{
                 ec_add, ec_mul, ec_pairing,
                 evm::{blake2f, ec_recover, modexp},
-                PrecompileContext, PrecompileError, PrecompileFn,
+                point_evaluation, PrecompileContext, PrecompileError, PrecompileFn,
             },
             System,
         };
@@ -516,6 +516,7 @@ mod tests {
                 (ec_pairing, "bn256Pairing"),
                 (modexp, "modexp"),
                 (modexp, "modexp_eip2565"),
+                (point_evaluation, "pointEvaluation"),
             ];
 
             let rt = MockRuntime::default();
@@ -543,7 +544,7 @@ mod tests {
             }
 
             let failures: &[(PrecompileFn<MockRuntime>, &'static str)] =
-                &[(blake2f, "fail-blake2f")];
+                &[(blake2f, "fail-blake2f"), (point_evaluation, "fail-pointEvaluation")];
 
             let rt = MockRuntime::default();
             let mut system = System::create(&rt).unwrap();
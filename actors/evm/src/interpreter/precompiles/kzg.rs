@@ -0,0 +1,125 @@
+//! The KZG point evaluation precompile, as specified by
+//! [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844#point-evaluation-precompile).
+
+use blst::{
+    blst_p1, blst_p1_add_or_double, blst_p1_affine, blst_p1_affine_in_g1, blst_p1_affine_is_inf,
+    blst_p1_cneg, blst_p1_from_affine, blst_p1_generator, blst_p1_mult, blst_p1_to_affine,
+    blst_p1_uncompress, blst_p2, blst_p2_add_or_double, blst_p2_affine, blst_p2_affine_generator,
+    blst_p2_cneg, blst_p2_from_affine, blst_p2_generator, blst_p2_mult, blst_p2_to_affine,
+    blst_p2_uncompress, BLST_ERROR,
+};
+use fil_actors_runtime::runtime::Runtime;
+use fvm_shared::crypto::hash::SupportedHashes;
+
+use crate::interpreter::{precompiles::PrecompileError, System};
+
+use super::bls12_381::pairing_is_one;
+use super::{PrecompileContext, PrecompileResult};
+
+const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+const FIELD_ELEMENTS_PER_BLOB: u64 = 4096;
+
+/// The order of the BLS12-381 scalar field, big-endian.
+const BLS_MODULUS: [u8; 32] =
+    hex_literal::hex!("73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001");
+
+/// `[τ]₂`, the only part of the KZG trusted setup needed to verify a proof for a single point.
+/// This is the second G2 point of the setup produced by the Ethereum KZG ceremony, compressed.
+const TAU_G2: [u8; 96] = hex_literal::hex!(
+    "b5bfd7dd8cdeb128843bc287230af38926187075cbfbefa81009a2ce615ac53d2914e5870cb452d2afaaab24f3499f72"
+    "185cbfee53492714734429b7b38608e23926c911cceceac9a36851477ba4c60b087041de621000edc98edada20c1def2"
+);
+
+/// Reads a scalar field element, rejecting values not less than the modulus.
+/// Returns it little-endian, as blst multiplies by.
+fn read_field_element(input: &[u8]) -> Result<[u8; 32], PrecompileError> {
+    // Big-endian byte arrays of the same length compare as the integers they encode.
+    if input >= &BLS_MODULUS[..] {
+        return Err(PrecompileError::InvalidInput);
+    }
+    let mut scalar = [0u8; 32];
+    scalar.copy_from_slice(input);
+    scalar.reverse();
+    Ok(scalar)
+}
+
+/// Decompresses a G1 point, checking that it's in the subgroup.
+fn read_g1_compressed(input: &[u8]) -> Result<blst_p1, PrecompileError> {
+    let mut affine = blst_p1_affine::default();
+    if unsafe { blst_p1_uncompress(&mut affine, input.as_ptr()) } != BLST_ERROR::BLST_SUCCESS {
+        return Err(PrecompileError::InvalidInput);
+    }
+    // Decompression checks the point is on the curve, but not that it's in the subgroup.
+    if !unsafe { blst_p1_affine_is_inf(&affine) || blst_p1_affine_in_g1(&affine) } {
+        return Err(PrecompileError::InvalidInput);
+    }
+    let mut point = blst_p1::default();
+    unsafe { blst_p1_from_affine(&mut point, &affine) };
+    Ok(point)
+}
+
+/// verify a KZG proof that a blob commitment evaluates to `y` at `z`
+pub(super) fn point_evaluation<RT: Runtime>(
+    system: &mut System<RT>,
+    input: &[u8],
+    _: PrecompileContext,
+) -> PrecompileResult {
+    if input.len() != 192 {
+        return Err(PrecompileError::IncorrectInputSize);
+    }
+    let versioned_hash = &input[..32];
+    let z = &input[32..64];
+    let y = &input[64..96];
+    let commitment = &input[96..144];
+    let proof = &input[144..192];
+
+    let mut expected_hash = system.rt.hash(SupportedHashes::Sha2_256, commitment);
+    expected_hash[0] = VERSIONED_HASH_VERSION_KZG;
+    if versioned_hash != expected_hash {
+        return Err(PrecompileError::InvalidInput);
+    }
+
+    let z = read_field_element(z)?;
+    let y = read_field_element(y)?;
+    let commitment = read_g1_compressed(commitment)?;
+    let mut proof = read_g1_compressed(proof)?;
+
+    // Checks that e(commitment - [y]₁, [1]₂) * e(-proof, [τ]₂ - [z]₂) = 1.
+    let mut commitment_minus_y = blst_p1_affine::default();
+    let mut neg_proof = blst_p1_affine::default();
+    let mut tau_minus_z = blst_p2_affine::default();
+    unsafe {
+        let mut neg_y = blst_p1::default();
+        blst_p1_mult(&mut neg_y, blst_p1_generator(), y.as_ptr(), 256);
+        blst_p1_cneg(&mut neg_y, true);
+        let mut sum = blst_p1::default();
+        blst_p1_add_or_double(&mut sum, &commitment, &neg_y);
+        blst_p1_to_affine(&mut commitment_minus_y, &sum);
+
+        blst_p1_cneg(&mut proof, true);
+        blst_p1_to_affine(&mut neg_proof, &proof);
+
+        let mut tau = blst_p2_affine::default();
+        if blst_p2_uncompress(&mut tau, TAU_G2.as_ptr()) != BLST_ERROR::BLST_SUCCESS {
+            return Err(PrecompileError::InvalidInput);
+        }
+        let mut tau_g2 = blst_p2::default();
+        blst_p2_from_affine(&mut tau_g2, &tau);
+        let mut neg_z = blst_p2::default();
+        blst_p2_mult(&mut neg_z, blst_p2_generator(), z.as_ptr(), 256);
+        blst_p2_cneg(&mut neg_z, true);
+        let mut sum = blst_p2::default();
+        blst_p2_add_or_double(&mut sum, &tau_g2, &neg_z);
+        blst_p2_to_affine(&mut tau_minus_z, &sum);
+    }
+    let g2 = unsafe { *blst_p2_affine_generator() };
+
+    if !pairing_is_one(&[(commitment_minus_y, g2), (neg_proof, tau_minus_z)]) {
+        return Err(PrecompileError::InvalidInput);
+    }
+
+    let mut ret = [0u8; 64];
+    ret[24..32].copy_from_slice(&FIELD_ELEMENTS_PER_BLOB.to_be_bytes());
+    ret[32..].copy_from_slice(&BLS_MODULUS);
+    Ok(ret.to_vec())
+}
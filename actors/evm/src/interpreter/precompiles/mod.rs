@@ -9,11 +9,20 @@ use crate::reader::OverflowError;
 
 use super::{CallKind, System};
 mod blake2f_impl;
+mod bls12_381;
+mod builtin;
 mod evm;
 mod fvm;
+mod kzg;
 
+use bls12_381::{
+    bls12_g1_add, bls12_g1_msm, bls12_g2_add, bls12_g2_msm, bls12_map_fp2_to_g2,
+    bls12_map_fp_to_g1, bls12_pairing_check,
+};
+use builtin::call_builtin_getter;
 use evm::{blake2f, ec_add, ec_mul, ec_pairing, ec_recover, identity, modexp, ripemd160, sha256};
 use fvm::{call_actor, call_actor_id, lookup_delegated_address, resolve_address};
+use kzg::point_evaluation;
 
 type PrecompileFn<RT> = fn(&mut System<RT>, &[u8], PrecompileContext) -> PrecompileResult;
 pub type PrecompileResult = Result<Vec<u8>, PrecompileError>;
@@ -41,25 +50,34 @@ pub struct Precompiles<RT>(PhantomData<RT>);
 
 impl<RT: Runtime> Precompiles<RT> {
     /// FEVM specific precompiles (0xfe prefix)
-    const NATIVE_PRECOMPILES: PrecompileTable<RT, 5> = PrecompileTable([
+    const NATIVE_PRECOMPILES: PrecompileTable<RT, 6> = PrecompileTable([
         Some(resolve_address::<RT>),          // 0xfe00..01
         Some(lookup_delegated_address::<RT>), // 0xfe00..02
         Some(call_actor::<RT>),               // 0xfe00..03
         None,                                 // 0xfe00..04 DISABLED
         Some(call_actor_id::<RT>),            // 0xfe00..05
+        Some(call_builtin_getter::<RT>),      // 0xfe00..06
     ]);
 
     /// EVM specific precompiles
-    const EVM_PRECOMPILES: PrecompileTable<RT, 9> = PrecompileTable([
-        Some(ec_recover::<RT>), // 0x01 ecrecover
-        Some(sha256::<RT>),     // 0x02 SHA2-256
-        Some(ripemd160::<RT>),  // 0x03 ripemd160
-        Some(identity::<RT>),   // 0x04 identity
-        Some(modexp::<RT>),     // 0x05 modexp
-        Some(ec_add::<RT>),     // 0x06 ecAdd
-        Some(ec_mul::<RT>),     // 0x07 ecMul
-        Some(ec_pairing::<RT>), // 0x08 ecPairing
-        Some(blake2f::<RT>),    // 0x09 blake2f
+    const EVM_PRECOMPILES: PrecompileTable<RT, 17> = PrecompileTable([
+        Some(ec_recover::<RT>),          // 0x01 ecrecover
+        Some(sha256::<RT>),              // 0x02 SHA2-256
+        Some(ripemd160::<RT>),           // 0x03 ripemd160
+        Some(identity::<RT>),            // 0x04 identity
+        Some(modexp::<RT>),              // 0x05 modexp
+        Some(ec_add::<RT>),              // 0x06 ecAdd
+        Some(ec_mul::<RT>),              // 0x07 ecMul
+        Some(ec_pairing::<RT>),          // 0x08 ecPairing
+        Some(blake2f::<RT>),             // 0x09 blake2f
+        Some(point_evaluation::<RT>),    // 0x0a point evaluation
+        Some(bls12_g1_add::<RT>),        // 0x0b BLS12_G1ADD
+        Some(bls12_g1_msm::<RT>),        // 0x0c BLS12_G1MSM
+        Some(bls12_g2_add::<RT>),        // 0x0d BLS12_G2ADD
+        Some(bls12_g2_msm::<RT>),        // 0x0e BLS12_G2MSM
+        Some(bls12_pairing_check::<RT>), // 0x0f BLS12_PAIRING_CHECK
+        Some(bls12_map_fp_to_g1::<RT>),  // 0x10 BLS12_MAP_FP_TO_G1
+        Some(bls12_map_fp2_to_g2::<RT>), // 0x11 BLS12_MAP_FP2_TO_G2
     ]);
 
     fn lookup_precompile(addr: &EthAddress) -> Option<PrecompileFn<RT>> {
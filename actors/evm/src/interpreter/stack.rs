@@ -32,6 +32,12 @@ impl Stack {
         self.stack.is_empty()
     }
 
+    /// The stack contents, bottom first.
+    #[inline]
+    pub fn as_slice(&self) -> &[U256] {
+        &self.stack
+    }
+
     #[inline(always)]
     pub fn push_unchecked(&mut self, value: U256) {
         self.stack.push(value);
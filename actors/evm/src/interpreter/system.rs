@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::collections::btree_map::{BTreeMap, Entry};
 
 use fil_actors_evm_shared::{address::EthAddress, uints::U256};
 use fil_actors_runtime::{
@@ -16,7 +17,7 @@ use fvm_shared::sys::SendFlags;
 use fvm_shared::{MethodNum, Response, IPLD_RAW, METHOD_SEND};
 use multihash::Code;
 
-use crate::state::{State, Tombstone};
+use crate::state::{State, TopLevelMessage, TransientData};
 use crate::BytecodeHash;
 
 use cid::Cid;
@@ -44,7 +45,8 @@ use fvm_ipld_kamt::{AsHashedKey, Config as KamtConfig, Kamt};
 //
 // The following values have been set by looking at how the charts evolved
 // with the test contract. They might not be the best for other contracts.
-const KAMT_CONFIG: KamtConfig = KamtConfig { min_data_depth: 0, bit_width: 5, max_array_width: 1 };
+pub(crate) const KAMT_CONFIG: KamtConfig =
+    KamtConfig { min_data_depth: 0, bit_width: 5, max_array_width: 1 };
 
 pub struct StateHashAlgorithm;
 
@@ -81,6 +83,15 @@ impl EvmBytecode {
     }
 }
 
+/// A storage slot read or written during the current call.
+#[derive(Clone, Copy, Debug)]
+struct CachedSlot {
+    /// The value in the storage KAMT.
+    original: U256,
+    /// The value seen by the contract, to be written back on flush if it differs.
+    current: U256,
+}
+
 /// Platform Abstraction Layer
 /// that bridges the FVM world to EVM world
 pub struct System<'r, RT: Runtime> {
@@ -91,6 +102,13 @@ pub struct System<'r, RT: Runtime> {
     bytecode: Option<EvmBytecode>,
     /// The contract's EVM storage slots.
     slots: StateKamt<RT::Blockstore>,
+    /// Storage slots accessed since the last reload. Writes are applied to `slots` on flush.
+    slot_cache: BTreeMap<U256, CachedSlot>,
+    /// The contract's EIP-1153 transient storage slots, for the current top-level message.
+    transient_slots: StateKamt<RT::Blockstore>,
+    /// Whether transient storage has been written during the current top-level message, and
+    /// must be saved.
+    transient_used: bool,
     /// The contracts "nonce" (incremented when creating new actors).
     pub(crate) nonce: u64,
     /// The last saved state root. None if the current state hasn't been saved yet.
@@ -102,7 +120,10 @@ pub struct System<'r, RT: Runtime> {
 
     /// This is "some" if the actor is currently a "zombie". I.e., it has selfdestructed, but the
     /// current message is still executing. `System` cannot load a contracts state with a
-    pub(crate) tombstone: Option<Tombstone>,
+    pub(crate) tombstone: Option<TopLevelMessage>,
+
+    /// The top-level message that created the contract, if known.
+    pub(crate) creation_message: Option<TopLevelMessage>,
 }
 
 impl<'r, RT: Runtime> System<'r, RT> {
@@ -113,13 +134,17 @@ impl<'r, RT: Runtime> System<'r, RT> {
         let store = rt.store().clone();
         Self {
             rt,
-            slots: StateKamt::new_with_config(store, KAMT_CONFIG.clone()),
+            slots: StateKamt::new_with_config(store.clone(), KAMT_CONFIG.clone()),
+            slot_cache: BTreeMap::new(),
+            transient_slots: StateKamt::new_with_config(store, KAMT_CONFIG.clone()),
+            transient_used: false,
             nonce: 1,
             saved_state_root: None,
             bytecode: None,
             readonly,
             randomness: None,
             tombstone: None,
+            creation_message: None,
         }
     }
 
@@ -177,17 +202,23 @@ impl<'r, RT: Runtime> System<'r, RT> {
         }
 
         let read_only = rt.read_only();
+        let (transient_slots, transient_used) =
+            load_transient_slots(rt, store.clone(), &state.transient_data)?;
 
         Ok(Self {
             rt,
             slots: StateKamt::load_with_config(&state.contract_state, store, KAMT_CONFIG.clone())
                 .context_code(ExitCode::USR_ILLEGAL_STATE, "state not in blockstore")?,
+            slot_cache: BTreeMap::new(),
+            transient_slots,
+            transient_used,
             nonce: state.nonce,
             saved_state_root: Some(state_root),
             bytecode: Some(EvmBytecode::new(state.bytecode, state.bytecode_hash)),
             readonly: read_only,
             randomness: None,
             tombstone: state.tombstone,
+            creation_message: state.creation_message,
         })
     }
 
@@ -272,6 +303,18 @@ impl<'r, RT: Runtime> System<'r, RT> {
             // set empty bytecode hashes
             None => self.set_bytecode(&[])?,
         };
+        let transient_data = if self.transient_used {
+            Some(TransientData {
+                transient_data_state: self.transient_slots.flush().context_code(
+                    ExitCode::USR_ILLEGAL_STATE,
+                    "failed to flush transient storage",
+                )?,
+                transient_data_lifespan: crate::current_message(self.rt),
+            })
+        } else {
+            None
+        };
+        self.write_back_slots()?;
         let new_root = self
             .rt
             .store()
@@ -285,6 +328,8 @@ impl<'r, RT: Runtime> System<'r, RT> {
                     )?,
                     nonce: self.nonce,
                     tombstone: self.tombstone,
+                    transient_data,
+                    creation_message: self.creation_message,
                 },
                 Code::Blake2b256,
             )
@@ -316,6 +361,19 @@ impl<'r, RT: Runtime> System<'r, RT> {
         self.slots
             .set_root(&state.contract_state)
             .context_code(ExitCode::USR_ILLEGAL_STATE, "state not in blockstore")?;
+        // We flushed before the state changed, so the cache holds no pending writes.
+        self.slot_cache.clear();
+        // Transient data can't expire during the current message, so can only have been replaced
+        // by a re-entrant call writing to it.
+        if let Some(data) = state
+            .transient_data
+            .filter(|data| data.transient_data_lifespan == crate::current_message(self.rt))
+        {
+            self.transient_slots
+                .set_root(&data.transient_data_state)
+                .context_code(ExitCode::USR_ILLEGAL_STATE, "transient storage not in blockstore")?;
+            self.transient_used = true;
+        }
         self.nonce = state.nonce;
         self.saved_state_root = Some(root);
         self.bytecode = Some(EvmBytecode::new(state.bytecode, state.bytecode_hash));
@@ -358,29 +416,83 @@ impl<'r, RT: Runtime> System<'r, RT> {
 
     /// Get value of a storage key.
     pub fn get_storage(&mut self, key: U256) -> Result<U256, ActorError> {
+        Ok(self.cached_slot(key)?.current)
+    }
+
+    /// Set value of a storage key.
+    pub fn set_storage(&mut self, key: U256, value: U256) -> Result<(), ActorError> {
+        let slot = self.cached_slot(key)?;
+        let changed = slot.current != value;
+        slot.current = value;
+
+        if changed {
+            self.saved_state_root = None; // dirty.
+        };
+        Ok(())
+    }
+
+    /// Looks up a storage slot in the cache, loading it from the KAMT on a miss.
+    fn cached_slot(&mut self, key: U256) -> Result<&mut CachedSlot, ActorError> {
+        match self.slot_cache.entry(key) {
+            Entry::Occupied(e) => Ok(e.into_mut()),
+            Entry::Vacant(e) => {
+                let value = self
+                    .slots
+                    .get(&key)
+                    .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to get storage slot")?
+                    .cloned()
+                    .unwrap_or_default();
+                Ok(e.insert(CachedSlot { original: value, current: value }))
+            }
+        }
+    }
+
+    /// Applies cached writes to the storage KAMT, skipping slots that end up unchanged.
+    fn write_back_slots(&mut self) -> Result<(), ActorError> {
+        for (key, slot) in self.slot_cache.iter_mut() {
+            if slot.current == slot.original {
+                continue;
+            }
+            if slot.current.is_zero() {
+                self.slots
+                    .delete(key)
+                    .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to clear storage slot")?;
+            } else {
+                self.slots
+                    .set(*key, slot.current)
+                    .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to update storage slot")?;
+            }
+            slot.original = slot.current;
+        }
+        Ok(())
+    }
+
+    /// Get value of a transient storage key.
+    pub fn get_transient_storage(&mut self, key: U256) -> Result<U256, ActorError> {
         Ok(self
-            .slots
+            .transient_slots
             .get(&key)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to clear storage slot")?
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to get transient storage slot")?
             .cloned()
             .unwrap_or_default())
     }
 
-    /// Set value of a storage key.
-    pub fn set_storage(&mut self, key: U256, value: U256) -> Result<(), ActorError> {
+    /// Set value of a transient storage key.
+    pub fn set_transient_storage(&mut self, key: U256, value: U256) -> Result<(), ActorError> {
         let changed = if value.is_zero() {
-            self.slots
-                .delete(&key)
-                .map(|v| v.is_some())
-                .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to clear storage slot")?
+            self.transient_slots.delete(&key).map(|v| v.is_some()).context_code(
+                ExitCode::USR_ILLEGAL_STATE,
+                "failed to clear transient storage slot",
+            )?
         } else {
-            self.slots
-                .set(key, value)
-                .map(|v| v != Some(value))
-                .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to update storage slot")?
+            self.transient_slots.set(key, value).map(|v| v != Some(value)).context_code(
+                ExitCode::USR_ILLEGAL_STATE,
+                "failed to update transient storage slot",
+            )?
         };
 
         if changed {
+            self.transient_used = true;
             self.saved_state_root = None; // dirty.
         };
         Ok(())
@@ -441,9 +553,36 @@ impl<'r, RT: Runtime> System<'r, RT> {
         }
     }
 
+    /// Returns true if the contract was created during the currently executing top-level message.
+    pub fn created_in_current_message(&self) -> bool {
+        self.creation_message == Some(crate::current_message(self.rt))
+    }
+
     /// Mark ourselves as "selfdestructed".
     pub fn mark_selfdestructed(&mut self) {
         self.saved_state_root = None;
-        self.tombstone = Some(crate::current_tombstone(self.rt));
+        self.tombstone = Some(crate::current_message(self.rt));
+    }
+}
+
+/// Loads the transient storage saved in the state if it was written during the currently
+/// executing top-level message, or returns empty transient storage otherwise.
+/// Also returns whether the storage was loaded.
+fn load_transient_slots<RT: Runtime>(
+    rt: &RT,
+    store: RT::Blockstore,
+    transient_data: &Option<TransientData>,
+) -> Result<(StateKamt<RT::Blockstore>, bool), ActorError> {
+    match transient_data {
+        Some(data) if data.transient_data_lifespan == crate::current_message(rt) => {
+            let slots =
+                StateKamt::load_with_config(&data.transient_data_state, store, KAMT_CONFIG.clone())
+                    .context_code(
+                        ExitCode::USR_ILLEGAL_STATE,
+                        "transient storage not in blockstore",
+                    )?;
+            Ok((slots, true))
+        }
+        _ => Ok((StateKamt::new_with_config(store, KAMT_CONFIG.clone()), false)),
     }
 }
@@ -0,0 +1,207 @@
+//! An opt-in, per-thread step tracer producing geth-style `structLogs`.
+//!
+//! Tracing is meant for debugging contracts in native tests (e.g. the test VM) and is compiled
+//! out of the Wasm actor. Call [`start`] before invoking a contract, then [`finish`] to collect
+//! the steps executed by that contract and any contracts it called.
+
+use std::cell::{Cell, RefCell};
+
+use serde::Serialize;
+
+use super::stack::Stack;
+
+/// The maximum number of stack items recorded per step, counted from the top. No instruction
+/// reaches deeper than this (SWAP16 touches the 17th item).
+const MAX_TRACED_STACK: usize = 17;
+
+thread_local! {
+    static STEPS: RefCell<Option<Vec<StructLog>>> = const { RefCell::new(None) };
+    static DEPTH: Cell<u64> = const { Cell::new(0) };
+}
+
+/// A single executed instruction, as reported by geth's struct logger.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+    pub pc: u64,
+    pub op: &'static str,
+    pub gas: u64,
+    pub gas_cost: u64,
+    /// The call depth, starting at 1 for the outermost contract.
+    pub depth: u64,
+    /// The top of the stack before the instruction executed, bottom first, as hex words.
+    pub stack: Vec<String>,
+    pub mem_size: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A finished trace, serializing as the `structLogs` part of a `debug_traceTransaction` result.
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Trace {
+    pub struct_logs: Vec<StructLog>,
+}
+
+/// Starts tracing on this thread, discarding any previous unfinished trace.
+pub fn start() {
+    STEPS.with(|s| *s.borrow_mut() = Some(Vec::new()));
+}
+
+/// Stops tracing on this thread and returns the steps recorded since [`start`].
+pub fn finish() -> Trace {
+    Trace { struct_logs: STEPS.with(|s| s.borrow_mut().take()).unwrap_or_default() }
+}
+
+pub(crate) fn is_active() -> bool {
+    STEPS.with(|s| s.borrow().is_some())
+}
+
+/// Tracks the call depth while a (possibly nested) contract invocation is being traced.
+pub(crate) struct DepthGuard {
+    pub depth: u64,
+}
+
+impl DepthGuard {
+    pub fn enter() -> Self {
+        let depth = DEPTH.with(|d| {
+            d.set(d.get() + 1);
+            d.get()
+        });
+        DepthGuard { depth }
+    }
+}
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        DEPTH.with(|d| d.set(d.get() - 1));
+    }
+}
+
+/// Snapshots the top of the stack, bottom first, as geth does.
+pub(crate) fn stack_top(stack: &Stack) -> Vec<String> {
+    let items = stack.as_slice();
+    items[items.len().saturating_sub(MAX_TRACED_STACK)..]
+        .iter()
+        .map(|v| format!("{:#x}", v))
+        .collect()
+}
+
+/// Records a step, returning its index in the trace.
+pub(crate) fn record(step: StructLog) -> usize {
+    STEPS.with(|s| match s.borrow_mut().as_mut() {
+        Some(steps) => {
+            steps.push(step);
+            steps.len() - 1
+        }
+        None => 0,
+    })
+}
+
+/// Updates a previously recorded step, once its outcome is known.
+pub(crate) fn update(index: usize, f: impl FnOnce(&mut StructLog)) {
+    STEPS.with(|s| {
+        if let Some(step) = s.borrow_mut().as_mut().and_then(|steps| steps.get_mut(index)) {
+            f(step)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::evm_unit_test;
+
+    use super::{finish, start, StructLog};
+
+    #[test]
+    fn test_trace_steps() {
+        evm_unit_test! {
+            (rt) {
+                for gas in [100, 97, 97, 94, 94, 91, 91, 89, 89, 83] {
+                    rt.expect_gas_available(gas);
+                }
+            }
+            (m) {
+                PUSH1; 0x02;
+                PUSH1; 0x03;
+                ADD;
+                PUSH0;
+                MSTORE;
+            }
+            start();
+            m.execute().expect("execution failed");
+            let trace = finish();
+
+            let step = |pc, op, gas, gas_cost, stack: &[&str], mem_size| StructLog {
+                pc,
+                op,
+                gas,
+                gas_cost,
+                depth: 1,
+                stack: stack.iter().map(|s| s.to_string()).collect(),
+                mem_size,
+                error: None,
+            };
+            assert_eq!(
+                trace.struct_logs,
+                vec![
+                    step(0, "PUSH1", 100, 3, &[], 0),
+                    step(2, "PUSH1", 97, 3, &["0x2"], 0),
+                    step(4, "ADD", 94, 3, &["0x2", "0x3"], 0),
+                    step(5, "PUSH0", 91, 2, &["0x5"], 0),
+                    step(6, "MSTORE", 89, 6, &["0x5", "0x0"], 0),
+                ]
+            );
+        };
+    }
+
+    #[test]
+    fn test_trace_error() {
+        evm_unit_test! {
+            (rt) {
+                rt.expect_gas_available(10);
+                rt.expect_gas_available(10);
+            }
+            (m) {
+                INVALID;
+            }
+            start();
+            assert!(m.execute().is_err());
+            let trace = finish();
+
+            assert_eq!(trace.struct_logs.len(), 1);
+            assert_eq!(trace.struct_logs[0].op, "INVALID");
+            assert!(trace.struct_logs[0].error.is_some());
+        };
+    }
+
+    #[test]
+    fn test_trace_inactive() {
+        evm_unit_test! {
+            (m) {
+                PUSH0;
+            }
+            m.execute().expect("execution failed");
+            assert!(finish().struct_logs.is_empty());
+        };
+    }
+
+    #[test]
+    fn test_trace_json() {
+        let log = StructLog {
+            pc: 1,
+            op: "ADD",
+            gas: 10,
+            gas_cost: 3,
+            depth: 1,
+            stack: vec!["0x1".into()],
+            mem_size: 32,
+            error: None,
+        };
+        let trace = super::Trace { struct_logs: vec![log] };
+        assert_eq!(
+            serde_json::to_string(&trace).unwrap(),
+            r#"{"structLogs":[{"pc":1,"op":"ADD","gas":10,"gasCost":3,"depth":1,"stack":["0x1"],"memSize":32}]}"#
+        );
+    }
+}
@@ -9,6 +9,7 @@ use fvm_ipld_encoding::BytesSer;
 use fvm_shared::address::Address;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
+use fvm_shared::version::NetworkVersion;
 
 use crate::interpreter::Outcome;
 use crate::interpreter::{execute, Bytecode, ExecutionState, System};
@@ -25,6 +26,8 @@ pub mod ext;
 pub mod interpreter;
 pub(crate) mod reader;
 mod state;
+mod storage_range;
+pub mod testing;
 mod types;
 
 pub use state::*;
@@ -41,6 +44,10 @@ pub const EVM_CONTRACT_ILLEGAL_MEMORY_ACCESS: ExitCode = ExitCode::new(38);
 pub const EVM_CONTRACT_BAD_JUMPDEST: ExitCode = ExitCode::new(39);
 pub const EVM_CONTRACT_SELFDESTRUCT_FAILED: ExitCode = ExitCode::new(40);
 
+/// The network version from which SELFDESTRUCT follows EIP-6780, only deleting contracts created
+/// during the current top-level message.
+pub(crate) const EIP_6780_NETWORK_VERSION: NetworkVersion = NetworkVersion::new(22);
+
 const EVM_MAX_RESERVED_METHOD: u64 = 1023;
 pub const NATIVE_METHOD_SIGNATURE: &str = "handle_filecoin_method(uint64,uint64,bytes)";
 pub const NATIVE_METHOD_SELECTOR: [u8; 4] = [0x86, 0x8e, 0x10, 0xc4];
@@ -65,14 +72,15 @@ pub enum Method {
     GetBytecodeHash = 4,
     GetStorageAt = 5,
     InvokeContractDelegate = 6,
+    GetStorageRange = frc42_dispatch::method_hash!("GetStorageRange"),
     InvokeContract = frc42_dispatch::method_hash!("InvokeEVM"),
 }
 
 pub struct EvmContractActor;
 
-/// Returns a tombstone for the currently executing message.
-pub(crate) fn current_tombstone(rt: &impl Runtime) -> Tombstone {
-    Tombstone { origin: rt.message().origin().id().unwrap(), nonce: rt.message().nonce() }
+/// Returns the currently executing top-level message.
+pub(crate) fn current_message(rt: &impl Runtime) -> TopLevelMessage {
+    TopLevelMessage { origin: rt.message().origin().id().unwrap(), nonce: rt.message().nonce() }
 }
 
 /// Returns true if the contract is "dead". A contract is dead if:
@@ -84,7 +92,7 @@ pub(crate) fn current_tombstone(rt: &impl Runtime) -> Tombstone {
 /// Specifically, this lets us mark the contract as "self-destructed" but keep it alive until the
 /// current top-level message finishes executing.
 pub(crate) fn is_dead(rt: &impl Runtime, state: &State) -> bool {
-    state.tombstone.map_or(false, |t| t != current_tombstone(rt))
+    state.tombstone.map_or(false, |t| t != current_message(rt))
 }
 
 fn load_bytecode(bs: &impl Blockstore, cid: &Cid) -> Result<Option<Bytecode>, ActorError> {
@@ -104,6 +112,8 @@ fn initialize_evm_contract(
     caller: EthAddress,
     initcode: Vec<u8>,
 ) -> Result<(), ActorError> {
+    system.creation_message = Some(current_message(system.rt));
+
     // Lookup our Ethereum address.
     let receiver_fil_addr = system.rt.message().receiver();
     let receiver_eth_addr = system.resolve_ethereum_address(&receiver_fil_addr).context_code(
@@ -325,6 +335,39 @@ impl EvmContractActor {
 
         Ok(GetStorageAtReturn { storage: val })
     }
+
+    /// Returns up to `limit` of the contract's storage entries with keys no lower than
+    /// `start_key`, in ascending key order. Unlike `GetStorageAt`, this is meant for off-chain
+    /// tooling (explorers, debuggers) and can be called by anyone.
+    pub fn storage_range(
+        rt: &impl Runtime,
+        params: GetStorageRangeParams,
+    ) -> Result<GetStorageRangeReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+
+        if params.limit == 0 || params.limit > MAX_STORAGE_RANGE_LIMIT {
+            return Err(actor_error!(
+                illegal_argument;
+                "limit must be between 1 and {}, got {}",
+                MAX_STORAGE_RANGE_LIMIT,
+                params.limit
+            ));
+        }
+
+        // A dead contract has no storage.
+        let state: State = rt.state()?;
+        if is_dead(rt, &state) {
+            return Ok(GetStorageRangeReturn { entries: Vec::new(), next_key: None });
+        }
+
+        let (entries, next_key) = storage_range::storage_range(
+            rt.store(),
+            &state.contract_state,
+            params.start_key,
+            params.limit as usize,
+        )?;
+        Ok(GetStorageRangeReturn { entries, next_key })
+    }
 }
 
 /// Format "filecoin_native_method" input parameters.
@@ -415,6 +458,7 @@ impl ActorCode for EvmContractActor {
         GetBytecode => bytecode,
         GetBytecodeHash => bytecode_hash,
         GetStorageAt => storage_at,
+        GetStorageRange => storage_range,
         InvokeContractDelegate => invoke_contract_delegate,
         Resurrect => resurrect,
         _ => handle_filecoin_method [raw],
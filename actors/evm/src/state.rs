@@ -6,18 +6,29 @@ use fvm_shared::ActorID;
 use cid::Cid;
 use fvm_ipld_encoding::strict_bytes;
 use fvm_ipld_encoding::tuple::*;
-use serde::{Deserialize, Serialize};
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_tuple::{Deserialize_tuple, Serialize_tuple};
 
-/// A tombstone indicating that the contract has been self-destructed.
+/// A top-level message, identified by its origin and nonce. Records when the contract was
+/// self-destructed, created, or last wrote transient data.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize_tuple, Deserialize_tuple)]
-pub struct Tombstone {
-    /// The message origin when this actor was self-destructed.
+pub struct TopLevelMessage {
+    /// The message origin.
     pub origin: ActorID,
-    /// The message nonce when this actor was self-destructed.
+    /// The message nonce.
     pub nonce: u64,
 }
 
+/// EIP-1153 transient storage, which lives only as long as the top-level message writing it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize_tuple, Deserialize_tuple)]
+pub struct TransientData {
+    /// The transient storage dictionary, a KAMT<U256, U256> like the contract state.
+    pub transient_data_state: Cid,
+    /// The message after which the transient data is no longer valid.
+    pub transient_data_lifespan: TopLevelMessage,
+}
+
 /// A Keccak256 digest of EVM bytecode.
 #[derive(Deserialize, Serialize, Clone, Copy, Eq, PartialEq)]
 #[serde(transparent)]
@@ -92,7 +103,7 @@ impl TryFrom<&[u8]> for BytecodeHash {
 
 /// Data stored by an EVM contract.
 /// This runs on the fvm-evm-runtime actor code cid.
-#[derive(Debug, Serialize_tuple, Deserialize_tuple)]
+#[derive(Debug, Serialize_tuple)]
 pub struct State {
     /// The EVM contract bytecode resulting from calling the
     /// initialization code by the constructor.
@@ -127,14 +138,72 @@ pub struct State {
     ///    (recreated) by via CREATE/CREATE2.
     ///
     /// See https://github.com/filecoin-project/ref-fvm/issues/1174 for some context.
-    pub tombstone: Option<Tombstone>,
+    pub tombstone: Option<TopLevelMessage>,
+
+    /// Transient storage written during the current top-level message, if any.
+    ///
+    /// Transient storage must survive re-entrant calls to the contract, so is saved with the
+    /// state. It is treated as empty whenever its lifespan doesn't match the currently executing
+    /// top-level message.
+    ///
+    /// Absent from state written before transient storage was supported, so defaults to None.
+    pub transient_data: Option<TransientData>,
+
+    /// The top-level message that created (or last resurrected) this contract, if known.
+    ///
+    /// From network version 22, SELFDESTRUCT follows EIP-6780: it only sets a tombstone if the
+    /// contract was created during the currently executing top-level message. Otherwise, it only
+    /// sends away all funds.
+    ///
+    /// Absent from state written before this was recorded, so defaults to None.
+    pub creation_message: Option<TopLevelMessage>,
+}
+
+/// Deserialize the State like a tuple, defaulting the trailing fields that state written by
+/// earlier versions doesn't have.
+impl<'de> Deserialize<'de> for State {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct StateVisitor;
+
+        impl<'de> Visitor<'de> for StateVisitor {
+            type Value = State;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a tuple of 5 to 7 EVM state fields")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<State, A::Error> {
+                fn required<'de, A: SeqAccess<'de>, T: Deserialize<'de>>(
+                    seq: &mut A,
+                    i: usize,
+                ) -> Result<T, A::Error> {
+                    seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &StateVisitor))
+                }
+                Ok(State {
+                    bytecode: required(&mut seq, 0)?,
+                    bytecode_hash: required(&mut seq, 1)?,
+                    contract_state: required(&mut seq, 2)?,
+                    nonce: required(&mut seq, 3)?,
+                    tombstone: required(&mut seq, 4)?,
+                    transient_data: seq.next_element()?.unwrap_or_default(),
+                    creation_message: seq.next_element()?.unwrap_or_default(),
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(StateVisitor)
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use cid::Cid;
     use fvm_ipld_encoding::{from_slice, to_vec, BytesDe};
 
-    use crate::BytecodeHash;
+    use crate::{BytecodeHash, State, TopLevelMessage, TransientData};
 
     #[test]
     fn test_bytecode_hash_serde() {
@@ -143,6 +212,38 @@ mod test {
         assert_eq!(BytecodeHash::try_from(&decoded[..]).unwrap(), BytecodeHash::EMPTY);
     }
 
+    #[test]
+    fn test_decode_state_without_optional_fields() {
+        // State written before transient storage and creation messages were recorded.
+        let tombstone = Some(TopLevelMessage { origin: 100, nonce: 2 });
+        let encoded =
+            to_vec(&(Cid::default(), BytecodeHash::EMPTY, Cid::default(), 3u64, tombstone))
+                .unwrap();
+        let state: State = from_slice(&encoded).unwrap();
+        assert_eq!(state.bytecode_hash, BytecodeHash::EMPTY);
+        assert_eq!(state.nonce, 3);
+        assert_eq!(state.tombstone, tombstone);
+        assert!(state.transient_data.is_none());
+        assert!(state.creation_message.is_none());
+
+        // And the current state, with every field.
+        let state = State {
+            transient_data: Some(TransientData {
+                transient_data_state: Cid::default(),
+                transient_data_lifespan: TopLevelMessage { origin: 100, nonce: 4 },
+            }),
+            creation_message: Some(TopLevelMessage { origin: 100, nonce: 1 }),
+            ..state
+        };
+        let decoded: State = from_slice(&to_vec(&state).unwrap()).unwrap();
+        assert_eq!(decoded.transient_data, state.transient_data);
+        assert_eq!(decoded.creation_message, state.creation_message);
+
+        // Fewer than the original fields is an error.
+        let encoded = to_vec(&(Cid::default(), BytecodeHash::EMPTY, Cid::default(), 3u64)).unwrap();
+        assert!(from_slice::<State>(&encoded).is_err());
+    }
+
     #[test]
     fn test_bytecode_hash_format() {
         assert_eq!(
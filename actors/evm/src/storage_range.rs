@@ -0,0 +1,124 @@
+//! Paged iteration over contract storage.
+//!
+//! Keys are hashed to their big-endian bytes, so the KAMT's hashed key order is ascending key
+//! order, and iterating from the start key only reads the nodes on the way to it and those
+//! holding the page.
+
+use cid::Cid;
+use fil_actors_evm_shared::uints::U256;
+use fil_actors_runtime::{ActorError, AsActorError};
+use fvm_ipld_blockstore::Blockstore;
+use fvm_shared::error::ExitCode;
+
+use crate::interpreter::{StateKamt, KAMT_CONFIG};
+use crate::StorageEntry;
+
+/// Returns up to `limit` entries of the storage KAMT rooted at `root` with keys no lower than
+/// `start_key`, along with the key following them, if any.
+pub(crate) fn storage_range(
+    store: &impl Blockstore,
+    root: &Cid,
+    start_key: U256,
+    limit: usize,
+) -> Result<(Vec<StorageEntry>, Option<U256>), ActorError> {
+    let kamt = StateKamt::load_with_config(root, store, KAMT_CONFIG)
+        .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load contract storage")?;
+    let mut entries = Vec::with_capacity(limit);
+    let mut next_key = None;
+    kamt.for_each_from(&start_key, |key, value| {
+        if entries.len() == limit {
+            next_key = Some(*key);
+            return Ok(false);
+        }
+        entries.push(StorageEntry { key: *key, value: *value });
+        Ok(true)
+    })
+    .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to iterate contract storage")?;
+    Ok((entries, next_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use fil_actors_evm_shared::uints::U256;
+    use fvm_ipld_blockstore::tracking::TrackingBlockstore;
+    use fvm_ipld_blockstore::MemoryBlockstore;
+
+    use super::storage_range;
+    use crate::interpreter::{StateKamt, KAMT_CONFIG};
+    use crate::StorageEntry;
+
+    #[test]
+    fn ranges_match_iteration() {
+        let store = MemoryBlockstore::new();
+        let mut kamt = StateKamt::new_with_config(&store, KAMT_CONFIG);
+        // Small keys, sparse keys and keys with long common prefixes, which use extensions.
+        let mut keys: Vec<U256> = (0..40u64).map(U256::from).collect();
+        keys.extend((0..40u32).map(|i| U256::from(0x1234_5678u64 + i as u64) << (i * 5)));
+        keys.extend((0..8u64).map(|i| U256::MAX - U256::from(i * 1000)));
+        for (i, key) in keys.iter().enumerate() {
+            kamt.set(*key, U256::from(i + 1)).unwrap();
+        }
+        let root = kamt.flush().unwrap();
+
+        let mut all = Vec::new();
+        kamt.for_each(|key, value| {
+            all.push(StorageEntry { key: *key, value: *value });
+            Ok(())
+        })
+        .unwrap();
+        keys.sort();
+        keys.dedup();
+        assert_eq!(all.iter().map(|e| e.key).collect::<Vec<_>>(), keys);
+
+        for limit in [1, 3, 7, all.len(), all.len() + 1] {
+            // Page through the whole storage.
+            let mut pages = Vec::new();
+            let mut start_key = Some(U256::ZERO);
+            while let Some(key) = start_key {
+                let (entries, next_key) = storage_range(&store, &root, key, limit).unwrap();
+                assert!(entries.len() == limit || next_key.is_none());
+                pages.extend(entries);
+                start_key = next_key;
+            }
+            assert_eq!(pages, all);
+        }
+
+        // Starting between keys.
+        for entry in &all {
+            if entry.key.is_zero() {
+                continue;
+            }
+            let start_key = entry.key - 1;
+            let (entries, _) = storage_range(&store, &root, start_key, 2).unwrap();
+            let expected: Vec<_> =
+                all.iter().filter(|e| e.key >= start_key).take(2).cloned().collect();
+            assert_eq!(entries, expected);
+        }
+    }
+
+    #[test]
+    fn range_reads_only_the_page() {
+        let store = TrackingBlockstore::new(MemoryBlockstore::new());
+        let mut kamt = StateKamt::new_with_config(&store, KAMT_CONFIG);
+        for i in 0..4096u64 {
+            kamt.set(U256::from(i), U256::from(i + 1)).unwrap();
+        }
+        let root = kamt.flush().unwrap();
+        let all_reads = {
+            store.stats.take();
+            StateKamt::load_with_config(&root, &store, KAMT_CONFIG)
+                .unwrap()
+                .for_each(|_, _| Ok(()))
+                .unwrap();
+            store.stats.take().r
+        };
+
+        store.stats.take();
+        let (entries, next_key) = storage_range(&store, &root, U256::from(4000), 10).unwrap();
+        assert_eq!(entries.first().unwrap().key, U256::from(4000));
+        assert_eq!(next_key, Some(U256::from(4010)));
+        // Only the nodes on the way to the start key and those holding the page are read.
+        let reads = store.stats.take().r;
+        assert!(reads * 10 < all_reads, "{} reads, {} to read everything", reads, all_reads);
+    }
+}
@@ -0,0 +1,82 @@
+use fil_actors_runtime::{MessageAccumulator, FIRST_NON_SINGLETON_ADDR};
+use fvm_ipld_blockstore::Blockstore;
+use fvm_shared::IPLD_RAW;
+
+use crate::interpreter::{StateKamt, KAMT_CONFIG};
+use crate::{BytecodeHash, State, TopLevelMessage};
+
+pub struct StateSummary {
+    pub bytecode_hash: BytecodeHash,
+    pub nonce: u64,
+    pub tombstone: Option<TopLevelMessage>,
+}
+
+/// Checks internal invariants of EVM contract state.
+pub fn check_state_invariants<BS: Blockstore>(
+    state: &State,
+    store: &BS,
+) -> (StateSummary, MessageAccumulator) {
+    let acc = MessageAccumulator::default();
+
+    acc.require(
+        state.bytecode.codec() == IPLD_RAW,
+        format!("bytecode {} is not raw IPLD", state.bytecode),
+    );
+    match store.get(&state.bytecode) {
+        Ok(Some(bytecode)) => check_bytecode_hash(&acc, state, &bytecode),
+        Ok(None) => acc.add(format!("bytecode {} not found", state.bytecode)),
+        Err(e) => acc.add(format!("error loading bytecode {}: {e}", state.bytecode)),
+    }
+
+    match StateKamt::load_with_config(&state.contract_state, store, KAMT_CONFIG) {
+        Ok(slots) => {
+            let ret = slots.for_each(|_, _| Ok(()));
+            acc.require_no_error(ret, "error iterating contract state");
+        }
+        Err(e) => acc.add(format!("error loading contract state: {e}")),
+    }
+
+    if let Some(transient_data) = &state.transient_data {
+        match StateKamt::load_with_config(&transient_data.transient_data_state, store, KAMT_CONFIG)
+        {
+            Ok(slots) => {
+                let ret = slots.for_each(|_, _| Ok(()));
+                acc.require_no_error(ret, "error iterating transient storage");
+            }
+            Err(e) => acc.add(format!("error loading transient storage: {e}")),
+        }
+    }
+
+    if let Some(tombstone) = &state.tombstone {
+        acc.require(
+            tombstone.origin >= FIRST_NON_SINGLETON_ADDR,
+            format!("tombstone origin {} is a singleton actor", tombstone.origin),
+        );
+    }
+
+    let summary = StateSummary {
+        bytecode_hash: state.bytecode_hash,
+        nonce: state.nonce,
+        tombstone: state.tombstone,
+    };
+    (summary, acc)
+}
+
+/// Checks the bytecode hash is the keccak256 digest of the bytecode.
+/// This is only checked with the `testing` feature, which brings in a Keccak implementation.
+#[cfg(feature = "testing")]
+fn check_bytecode_hash(acc: &MessageAccumulator, state: &State, bytecode: &[u8]) {
+    use multihash::{Code, MultihashDigest};
+
+    let digest = Code::Keccak256.digest(bytecode);
+    acc.require(
+        digest.digest() == state.bytecode_hash.as_slice(),
+        format!(
+            "bytecode hash {} does not match keccak256 of bytecode {}",
+            state.bytecode_hash, state.bytecode
+        ),
+    );
+}
+
+#[cfg(not(feature = "testing"))]
+fn check_bytecode_hash(_acc: &MessageAccumulator, _state: &State, _bytecode: &[u8]) {}
@@ -65,3 +65,29 @@ pub struct DelegateCallReturn {
 pub struct GetStorageAtParams {
     pub storage_key: U256,
 }
+
+#[derive(Serialize_tuple, Deserialize_tuple)]
+pub struct GetStorageRangeParams {
+    /// The first storage key to return, inclusive.
+    pub start_key: U256,
+    /// The maximum number of entries to return, at most [`MAX_STORAGE_RANGE_LIMIT`].
+    pub limit: u64,
+}
+
+/// The maximum number of storage entries returned by a single `GetStorageRange` call.
+pub const MAX_STORAGE_RANGE_LIMIT: u64 = 1024;
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+pub struct StorageEntry {
+    pub key: U256,
+    pub value: U256,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+pub struct GetStorageRangeReturn {
+    /// The non-zero storage entries, in ascending key order.
+    pub entries: Vec<StorageEntry>,
+    /// The key to pass as `start_key` to fetch the next page, or `None` if there are no more
+    /// entries.
+    pub next_key: Option<U256>,
+}
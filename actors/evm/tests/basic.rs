@@ -7,6 +7,7 @@ use fil_actors_runtime::test_utils::*;
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::address::Address;
+use fvm_shared::error::ExitCode;
 
 mod util;
 
@@ -160,6 +161,65 @@ sstore";
     rt.verify();
 }
 
+#[test]
+fn basic_get_storage_range() {
+    let init_code = {
+        // Initialize a few storage entries during init, out of key order.
+        let init = r"
+push1 0x0c
+push1 0x03
+sstore
+push1 0x0a
+push1 0x01
+sstore
+push2 0x0100
+push2 0x0100
+sstore
+push1 0x0b
+push1 0x02
+sstore";
+        let body = r#"return"#;
+
+        asm::new_contract("get_storage_range", init, body).unwrap()
+    };
+
+    let rt = util::construct_and_verify(init_code);
+    rt.reset();
+
+    let get_range = |start_key: U256, limit: u64| {
+        rt.expect_validate_caller_any();
+        let params = evm::GetStorageRangeParams { start_key, limit };
+        let ret = rt.call::<evm::EvmContractActor>(
+            evm::Method::GetStorageRange as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        );
+        rt.verify();
+        rt.reset();
+        ret.map(|r| r.unwrap().deserialize::<evm::GetStorageRangeReturn>().unwrap())
+    };
+    let entry = |key: u64, value: u64| evm::StorageEntry { key: key.into(), value: value.into() };
+
+    // Entries are paged in ascending key order.
+    let page = get_range(U256::zero(), 2).unwrap();
+    assert_eq!(page.entries, vec![entry(1, 0x0a), entry(2, 0x0b)]);
+    assert_eq!(page.next_key, Some(U256::from(3)));
+
+    let page = get_range(page.next_key.unwrap(), 2).unwrap();
+    assert_eq!(page.entries, vec![entry(3, 0x0c), entry(0x100, 0x100)]);
+    assert_eq!(page.next_key, None);
+
+    // Starting past the last key returns an empty page.
+    let page = get_range(U256::from(0x101), 2).unwrap();
+    assert!(page.entries.is_empty());
+    assert_eq!(page.next_key, None);
+
+    // The limit is bounded.
+    for limit in [0, evm::MAX_STORAGE_RANGE_LIMIT + 1] {
+        let err = get_range(U256::zero(), limit).unwrap_err();
+        assert_eq!(err.exit_code(), ExitCode::USR_ILLEGAL_ARGUMENT);
+    }
+}
+
 #[test]
 fn test_push_last_byte() {
     // 60 01 # len
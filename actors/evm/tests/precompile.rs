@@ -3,9 +3,16 @@ mod asm;
 use fil_actors_evm_shared::{address::EthAddress, uints::U256};
 use fil_actors_runtime::{
     test_utils::{new_bls_addr, MockRuntime},
-    EAM_ACTOR_ID,
+    BatchReturn, EAM_ACTOR_ID, STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR,
+    VERIFIED_REGISTRY_ACTOR_ADDR,
 };
-use fvm_shared::{address::Address as FILAddress, econ::TokenAmount, error::ExitCode, METHOD_SEND};
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_shared::bigint::{bigint_ser::BigIntSer, BigInt};
+use fvm_shared::piece::PaddedPieceSize;
+use fvm_shared::{
+    address::Address as FILAddress, econ::TokenAmount, error::ExitCode, sys::SendFlags, METHOD_SEND,
+};
+use num_traits::Zero;
 
 mod util;
 
@@ -300,3 +307,143 @@ fn test_precompile_failure() {
     assert_eq!(&[1u8], result.as_slice());
     rt.reset();
 }
+
+#[test]
+fn test_builtin_getter() {
+    let (init, body) = util::PrecompileTest::test_runner_assembly();
+    let rt =
+        util::construct_and_verify(asm::new_contract("precompile-tester", &init, &body).unwrap());
+
+    // getDealTerm(uint64)
+    let mut input = hex_literal::hex!("1a329cbc").to_vec();
+    input.extend_from_slice(&U256::from(42).to_bytes());
+
+    rt.expect_send(
+        STORAGE_MARKET_ACTOR_ADDR,
+        frc42_dispatch::method_hash!("GetDealTerm"),
+        IpldBlock::serialize_cbor(&42u64).unwrap(),
+        TokenAmount::zero(),
+        Some(0),
+        SendFlags::READ_ONLY,
+        IpldBlock::serialize_cbor(&(100i64, 518400i64)).unwrap(),
+        ExitCode::OK,
+        None,
+    );
+
+    let mut expected = U256::from(100).to_bytes().to_vec();
+    expected.extend_from_slice(&U256::from(518400).to_bytes());
+
+    let mut test = PrecompileTest {
+        precompile_address: NativePrecompile::CallBuiltinGetter.eth_address(),
+        output_size: 64,
+        expected_exit_code: PrecompileExit::Success,
+        gas_avaliable: 10_000_000_000,
+        call_op: util::PrecompileCallOpcode::StaticCall,
+        input: input.clone(),
+        expected_return: expected,
+    };
+    test.run_test(&rt);
+
+    // a failed call reverts
+    rt.expect_send(
+        STORAGE_MARKET_ACTOR_ADDR,
+        frc42_dispatch::method_hash!("GetDealTerm"),
+        IpldBlock::serialize_cbor(&42u64).unwrap(),
+        TokenAmount::zero(),
+        Some(0),
+        SendFlags::READ_ONLY,
+        None,
+        ExitCode::USR_NOT_FOUND,
+        None,
+    );
+    test.run_test_expecting(&rt, vec![], PrecompileExit::Reverted);
+
+    // unknown selectors revert without calling anything
+    test.input = hex_literal::hex!("deadbeef").to_vec();
+    test.run_test_expecting(&rt, vec![], PrecompileExit::Reverted);
+}
+
+#[test]
+fn test_builtin_getter_miner_power_verifreg() {
+    let (init, body) = util::PrecompileTest::test_runner_assembly();
+    let rt =
+        util::construct_and_verify(asm::new_contract("precompile-tester", &init, &body).unwrap());
+
+    // getSectorSize(uint64)
+    let mut input = hex_literal::hex!("32961332").to_vec();
+    input.extend_from_slice(&U256::from(1000).to_bytes());
+    rt.expect_send(
+        FILAddress::new_id(1000),
+        frc42_dispatch::method_hash!("GetSectorSize"),
+        None,
+        TokenAmount::zero(),
+        Some(0),
+        SendFlags::READ_ONLY,
+        IpldBlock::serialize_cbor(&(32u64 << 30)).unwrap(),
+        ExitCode::OK,
+        None,
+    );
+    let mut test = PrecompileTest {
+        precompile_address: NativePrecompile::CallBuiltinGetter.eth_address(),
+        output_size: 32,
+        expected_exit_code: PrecompileExit::Success,
+        gas_avaliable: 10_000_000_000,
+        call_op: util::PrecompileCallOpcode::StaticCall,
+        input,
+        expected_return: U256::from(32u64 << 30).to_bytes().to_vec(),
+    };
+    test.run_test(&rt);
+
+    // minerRawPower(uint64)
+    let mut input = hex_literal::hex!("06e9f834").to_vec();
+    input.extend_from_slice(&U256::from(1000).to_bytes());
+    rt.expect_send(
+        STORAGE_POWER_ACTOR_ADDR,
+        frc42_dispatch::method_hash!("MinerRawPower"),
+        IpldBlock::serialize_cbor(&1000u64).unwrap(),
+        TokenAmount::zero(),
+        Some(0),
+        SendFlags::READ_ONLY,
+        IpldBlock::serialize_cbor(&(BigIntSer(&BigInt::from(1u64 << 40)), true)).unwrap(),
+        ExitCode::OK,
+        None,
+    );
+    let mut expected = U256::from(1u64 << 40).to_bytes().to_vec();
+    expected.extend_from_slice(&U256::from(1).to_bytes());
+    test.input = input;
+    test.output_size = 64;
+    test.expected_return = expected;
+    test.run_test(&rt);
+
+    // getClaim(uint64,uint64)
+    let data =
+        cid::Cid::new_v1(0xf101, cid::multihash::Multihash::wrap(0x1012, &[0xab; 32]).unwrap());
+    let claim = (1000u64, 101u64, data, PaddedPieceSize(2048), 100i64, 200i64, 10i64, 7u64);
+    let mut input = hex_literal::hex!("ba422358").to_vec();
+    input.extend_from_slice(&U256::from(1000).to_bytes());
+    input.extend_from_slice(&U256::from(5).to_bytes());
+    rt.expect_send(
+        VERIFIED_REGISTRY_ACTOR_ADDR,
+        frc42_dispatch::method_hash!("GetClaims"),
+        IpldBlock::serialize_cbor(&(1000u64, vec![5u64])).unwrap(),
+        TokenAmount::zero(),
+        Some(0),
+        SendFlags::READ_ONLY,
+        IpldBlock::serialize_cbor(&(BatchReturn::ok(1), vec![claim])).unwrap(),
+        ExitCode::OK,
+        None,
+    );
+    let data = data.to_bytes();
+    let mut expected = Vec::new();
+    // The eight head words, with the data as an offset into the tail.
+    for word in [1000, 101, 8 * 32, 2048, 100, 200, 10, 7] {
+        expected.extend_from_slice(&U256::from(word).to_bytes());
+    }
+    expected.extend_from_slice(&U256::from(data.len()).to_bytes());
+    expected.extend_from_slice(&data);
+    expected.resize(expected.len() + (32 - data.len() % 32) % 32, 0);
+    test.input = input;
+    test.output_size = expected.len() as u32;
+    test.expected_return = expected;
+    test.run_test(&rt);
+}
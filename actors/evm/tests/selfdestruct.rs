@@ -1,5 +1,6 @@
 use fil_actor_evm::{
-    EvmContractActor, Method, ResurrectParams, State, Tombstone, EVM_CONTRACT_SELFDESTRUCT_FAILED,
+    EvmContractActor, Method, ResurrectParams, State, TopLevelMessage,
+    EVM_CONTRACT_SELFDESTRUCT_FAILED,
 };
 use fil_actors_evm_shared::{address::EthAddress, uints::U256};
 use fil_actors_runtime::{test_utils::*, EAM_ACTOR_ADDR, INIT_ACTOR_ADDR};
@@ -9,6 +10,7 @@ use fvm_shared::{
     econ::TokenAmount,
     error::{ErrorNumber, ExitCode},
     sys::SendFlags,
+    version::NetworkVersion,
     MethodNum, METHOD_SEND,
 };
 use num_traits::Zero;
@@ -43,7 +45,7 @@ fn test_selfdestruct() {
 
     assert!(util::invoke_contract(&rt, &selfdestruct_params).is_empty());
     let state: State = rt.get_state();
-    assert_eq!(state.tombstone, Some(Tombstone { origin: 100, nonce: 0 }));
+    assert_eq!(state.tombstone, Some(TopLevelMessage { origin: 100, nonce: 0 }));
     rt.verify();
 
     // Calls still work.
@@ -94,6 +96,97 @@ fn test_selfdestruct() {
     rt.verify();
 }
 
+#[test]
+fn test_selfdestruct_eip6780_same_message() {
+    let bytecode = hex::decode(include_str!("contracts/selfdestruct.hex")).unwrap();
+
+    let contract = Address::new_id(100);
+    let beneficiary = Address::new_id(1001);
+    let token_amount = TokenAmount::from_whole(2);
+
+    let mut rt = util::init_construct_and_verify(bytecode, |rt| {
+        rt.actor_code_cids.borrow_mut().insert(contract, *EVM_ACTOR_CODE_ID);
+        rt.set_origin(contract);
+        rt.set_balance(token_amount.clone());
+    });
+    rt.network_version = NetworkVersion::new(22);
+
+    // The contract was created by the current message, so SELFDESTRUCT still deletes it.
+    let selfdestruct_params = hex::decode("35f46994").unwrap();
+    rt.expect_send_simple(beneficiary, METHOD_SEND, None, token_amount, None, ExitCode::OK);
+    assert!(util::invoke_contract(&rt, &selfdestruct_params).is_empty());
+    rt.verify();
+
+    let state: State = rt.get_state();
+    assert_eq!(state.tombstone, Some(TopLevelMessage { origin: 100, nonce: 0 }));
+
+    // And it's gone once the message is over.
+    rt.set_origin(beneficiary);
+    let returnone_params = hex::decode("901717d1").unwrap();
+    assert!(util::invoke_contract(&rt, &returnone_params).is_empty());
+    rt.verify();
+}
+
+#[test]
+fn test_selfdestruct_eip6780_later_message() {
+    let bytecode = hex::decode(include_str!("contracts/selfdestruct.hex")).unwrap();
+
+    let contract = Address::new_id(100);
+    let beneficiary = Address::new_id(1001);
+    let token_amount = TokenAmount::from_whole(2);
+
+    let mut rt = util::init_construct_and_verify(bytecode, |rt| {
+        rt.actor_code_cids.borrow_mut().insert(contract, *EVM_ACTOR_CODE_ID);
+        rt.set_origin(contract);
+    });
+    rt.network_version = NetworkVersion::new(22);
+
+    // Call from a message other than the one that created the contract.
+    rt.set_origin(beneficiary);
+    rt.set_balance(token_amount.clone());
+
+    // SELFDESTRUCT only sends away the funds.
+    let selfdestruct_params = hex::decode("35f46994").unwrap();
+    rt.expect_send_simple(beneficiary, METHOD_SEND, None, token_amount, None, ExitCode::OK);
+    assert!(util::invoke_contract(&rt, &selfdestruct_params).is_empty());
+    rt.verify();
+
+    let state: State = rt.get_state();
+    assert_eq!(state.tombstone, None);
+
+    // The contract keeps working in later messages.
+    rt.set_origin(Address::new_id(1002));
+    let returnone_params = hex::decode("901717d1").unwrap();
+    assert_eq!(U256::from_big_endian(&util::invoke_contract(&rt, &returnone_params)), U256::ONE);
+    rt.verify();
+}
+
+#[test]
+fn test_selfdestruct_before_eip6780_later_message() {
+    let bytecode = hex::decode(include_str!("contracts/selfdestruct.hex")).unwrap();
+
+    let contract = Address::new_id(100);
+    let beneficiary = Address::new_id(1001);
+    let token_amount = TokenAmount::from_whole(2);
+
+    let mut rt = util::init_construct_and_verify(bytecode, |rt| {
+        rt.actor_code_cids.borrow_mut().insert(contract, *EVM_ACTOR_CODE_ID);
+        rt.set_origin(contract);
+    });
+    rt.network_version = NetworkVersion::new(21);
+
+    // Before network version 22, SELFDESTRUCT deletes the contract whichever message created it.
+    rt.set_origin(beneficiary);
+    rt.set_balance(token_amount.clone());
+    let selfdestruct_params = hex::decode("35f46994").unwrap();
+    rt.expect_send_simple(beneficiary, METHOD_SEND, None, token_amount, None, ExitCode::OK);
+    assert!(util::invoke_contract(&rt, &selfdestruct_params).is_empty());
+    rt.verify();
+
+    let state: State = rt.get_state();
+    assert_eq!(state.tombstone, Some(TopLevelMessage { origin: 1001, nonce: 0 }));
+}
+
 #[test]
 fn test_selfdestruct_missing_beneficiary() {
     let bytecode = hex::decode(include_str!("contracts/selfdestruct.hex")).unwrap();
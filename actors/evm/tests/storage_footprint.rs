@@ -0,0 +1,213 @@
+//! Storage footprint benchmarks: the blockstore reads and writes a call makes dominate its gas
+//! cost, so these pin them for common workloads to guard against regressions. A change that
+//! moves a footprint should update the expected stats here.
+
+mod asm;
+mod env;
+
+use std::sync::Arc;
+
+use env::TestEnv;
+use ethers::prelude::abigen;
+use ethers::providers::Provider;
+use fil_actor_evm as evm;
+use fil_actors_evm_shared::{address::EthAddress, uints::U256};
+use fil_actors_runtime::test_utils::ACCOUNT_ACTOR_CODE_ID;
+use fvm_ipld_blockstore::tracking::BSStats;
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_ipld_encoding::{BytesSer, IPLD_RAW};
+use fvm_shared::address::Address;
+use fvm_shared::event::{ActorEvent, Entry, Flags};
+
+abigen!(SimpleCoin, "./tests/contracts/SimpleCoin.abi");
+
+const OWNER_ID: u64 = 10000;
+
+/// keccak256("Transfer(address,address,uint256)")
+const TRANSFER_TOPIC: [u8; 32] =
+    hex_literal::hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+fn transfer_event(from: EthAddress, to: EthAddress, amount: u64) -> ActorEvent {
+    let entry = |key: &str, value: [u8; 32]| Entry {
+        flags: Flags::FLAG_INDEXED_ALL,
+        key: key.to_string(),
+        codec: IPLD_RAW,
+        value: value.to_vec(),
+    };
+    ActorEvent {
+        entries: vec![
+            entry("t1", TRANSFER_TOPIC),
+            entry("t2", from.as_evm_word().to_bytes()),
+            entry("t3", to.as_evm_word().to_bytes()),
+            entry("d", U256::from(amount).to_bytes()),
+        ],
+    }
+}
+
+#[test]
+fn simplecoin_transfer_footprint() {
+    let owner = Address::new_id(OWNER_ID);
+    let mut env = TestEnv::new(owner);
+    env.deploy(include_str!("contracts/simplecoin.hex"));
+    env.runtime.set_caller(*ACCOUNT_ACTOR_CODE_ID, owner);
+
+    let (client, _mock) = Provider::mocked();
+    let contract = SimpleCoin::new(
+        ethers::core::types::Address::from_slice(EthAddress::from_id(OWNER_ID).as_ref()),
+        Arc::new(client),
+    );
+
+    env.clear_store_stats();
+    let mut transfer_stats = Vec::new();
+    for i in 0..5u64 {
+        let receiver = EthAddress::from_id(20000 + i);
+        env.runtime.expect_emitted_event(transfer_event(
+            EthAddress::from_id(OWNER_ID),
+            receiver,
+            100,
+        ));
+        let sent = env.call(contract.send_coin(
+            ethers::core::types::Address::from_slice(receiver.as_ref()),
+            ethers::types::U256::from(100u64),
+        ));
+        assert!(sent);
+        env.runtime.verify();
+        transfer_stats.push(env.take_store_stats());
+    }
+
+    let balance = env.call(contract.get_balance(ethers::core::types::Address::from_slice(
+        EthAddress::from_id(OWNER_ID).as_ref(),
+    )));
+    assert_eq!(balance, ethers::types::U256::from(9500u64));
+    let read_stats = env.take_store_stats();
+
+    // Each transfer reads and rewrites the state root and the touched storage nodes. The third
+    // transfer splits a storage node, and later ones read a bigger storage tree.
+    let expected = [
+        BSStats { r: 3, w: 2, br: 1484, bw: 217 },
+        BSStats { r: 3, w: 2, br: 1525, bw: 260 },
+        BSStats { r: 3, w: 2, br: 1568, bw: 301 },
+        BSStats { r: 3, w: 3, br: 1609, bw: 396 },
+        BSStats { r: 3, w: 2, br: 1617, bw: 350 },
+    ];
+    assert_eq!(transfer_stats, expected);
+
+    // Reads don't write anything.
+    assert_eq!(read_stats, BSStats { r: 3, w: 0, br: 1658, bw: 0 });
+}
+
+#[test]
+fn simplecoin_self_transfer_footprint() {
+    let owner = Address::new_id(OWNER_ID);
+    let mut env = TestEnv::new(owner);
+    env.deploy(include_str!("contracts/simplecoin.hex"));
+    env.runtime.set_caller(*ACCOUNT_ACTOR_CODE_ID, owner);
+
+    let (client, _mock) = Provider::mocked();
+    let contract = SimpleCoin::new(
+        ethers::core::types::Address::from_slice(EthAddress::from_id(OWNER_ID).as_ref()),
+        Arc::new(client),
+    );
+    let owner_eth = EthAddress::from_id(OWNER_ID);
+
+    env.clear_store_stats();
+    env.runtime.expect_emitted_event(transfer_event(owner_eth, owner_eth, 100));
+    let sent = env.call(contract.send_coin(
+        ethers::core::types::Address::from_slice(owner_eth.as_ref()),
+        ethers::types::U256::from(100u64),
+    ));
+    assert!(sent);
+    env.runtime.verify();
+
+    // The transfer loads and stores the owner's balance twice, leaving it unchanged, so the cache
+    // doesn't touch the storage tree and only the (identical) state root is written. Writing
+    // through to the storage tree instead rewrites its root as well:
+    // BSStats { r: 3, w: 2, br: 1484, bw: 176 }.
+    assert_eq!(env.take_store_stats(), BSStats { r: 3, w: 1, br: 1484, bw: 129 });
+}
+
+/// Writes `n` values to storage slot 0, counting down to a final value of 1.
+fn hot_slot_contract() -> Vec<u8> {
+    let init = "";
+    let body = r#"
+push1 0x00
+calldataload
+loop:
+jumpdest
+dup1
+iszero
+%push(done)
+jumpi
+dup1
+push1 0x00
+sstore
+push1 0x01
+swap1
+sub
+%push(loop)
+jump
+done:
+jumpdest
+stop
+"#;
+    asm::new_contract("hot-slot", init, body).unwrap()
+}
+
+/// Invokes a contract with a single word of call data, returning the call's storage stats.
+fn call_hot_slot(env: &mut TestEnv, n: u64) -> BSStats {
+    env.clear_store_stats();
+    env.runtime.expect_validate_caller_any();
+    let params = IpldBlock::serialize_cbor(&BytesSer(&U256::from(n).to_bytes())).unwrap();
+    env.runtime.call::<evm::EvmContractActor>(evm::Method::InvokeContract as u64, params).unwrap();
+    env.runtime.verify();
+    env.take_store_stats()
+}
+
+#[test]
+fn hot_slot_footprint() {
+    let mut results = Vec::new();
+    for n in [1, 10, 100] {
+        // Use a fresh contract each time, so every measured call changes the slot from 0 to 1.
+        let mut env = TestEnv::new(Address::new_id(OWNER_ID));
+        env.deploy(&hex::encode(hot_slot_contract()));
+        results.push(call_hot_slot(&mut env, n));
+    }
+
+    // Repeated writes to a slot reach the blockstore once: every call writes the state root and
+    // one storage node. The storage tree buffers its nodes until flushed, so this holds without
+    // the cache too; the cache saves the tree updates, which the blockstore doesn't see.
+    assert_eq!(results, vec![BSStats { r: 3, w: 2, br: 154, bw: 141 }; 3]);
+}
+
+/// Writes the word in the call data to storage slot 0, then sets the slot back to 0.
+fn restore_slot_contract() -> Vec<u8> {
+    let init = "";
+    let body = r#"
+push1 0x00
+calldataload
+push1 0x00
+sstore
+push1 0x00
+push1 0x00
+sstore
+stop
+"#;
+    asm::new_contract("restore-slot", init, body).unwrap()
+}
+
+#[test]
+fn restore_slot_footprint() {
+    let mut env = TestEnv::new(Address::new_id(OWNER_ID));
+    env.deploy(&hex::encode(restore_slot_contract()));
+
+    let root = *env.runtime.state.borrow();
+
+    // Writing 0 over 0 doesn't change anything, so nothing is written.
+    assert_eq!(call_hot_slot(&mut env, 0), BSStats { r: 3, w: 0, br: 144, bw: 0 });
+
+    // Writing 1 and then restoring 0 leaves storage unchanged too, so the cache doesn't touch the
+    // storage tree and only the (identical) state root is written. Without the cache, the storage
+    // tree is updated twice and its root rewritten as well: BSStats { r: 3, w: 2, br: 144, bw: 132 }.
+    assert_eq!(call_hot_slot(&mut env, 1), BSStats { r: 3, w: 1, br: 144, bw: 129 });
+    assert_eq!(root, *env.runtime.state.borrow());
+}
@@ -114,6 +114,7 @@ pub enum NativePrecompile {
     CallActor = 3,
     GetActorType = 4,
     CallActorId = 5,
+    CallBuiltinGetter = 6,
 }
 
 #[allow(dead_code)]
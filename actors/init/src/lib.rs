@@ -6,13 +6,17 @@ use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 
 use fil_actors_runtime::{
-    actor_dispatch, actor_error, extract_send_result, ActorContext, ActorError, AsActorError,
-    EAM_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    actor_dispatch, actor_error, deserialize_block, extract_send_result, ActorContext, ActorError,
+    AsActorError, EAM_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
 };
+use fvm_ipld_encoding::RawBytes;
 use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
+use fvm_shared::sys::SendFlags;
 use fvm_shared::{ActorID, METHOD_CONSTRUCTOR};
 use num_derive::FromPrimitive;
+use num_traits::Zero;
 
 pub use self::state::State;
 pub use self::types::*;
@@ -31,6 +35,11 @@ pub enum Method {
     Constructor = METHOD_CONSTRUCTOR,
     Exec = 2,
     Exec4 = 3,
+    // Method numbers derived from FRC-0042 standards
+    ExecDeterministicExported = frc42_dispatch::method_hash!("ExecDeterministic"),
+    ResolveAddressExported = frc42_dispatch::method_hash!("ResolveAddress"),
+    LookupDelegatedAddressExported = frc42_dispatch::method_hash!("LookupDelegatedAddress"),
+    NextActorIDExported = frc42_dispatch::method_hash!("NextActorID"),
 }
 
 /// Init actor
@@ -53,19 +62,7 @@ impl Actor {
 
         log::trace!("called exec; params.code_cid: {:?}", &params.code_cid);
 
-        let caller_code =
-            rt.get_actor_code_cid(&rt.message().caller().id().unwrap()).ok_or_else(|| {
-                actor_error!(illegal_state, "no code for caller as {}", rt.message().caller())
-            })?;
-
-        log::trace!("caller code CID: {:?}", &caller_code);
-
-        if !can_exec(rt, &caller_code, &params.code_cid) {
-            return Err(actor_error!(forbidden;
-                    "called type {} cannot exec actor type {}",
-                    &caller_code, &params.code_cid
-            ));
-        }
+        check_can_exec(rt, &params.code_cid)?;
 
         // Compute a re-org-stable address.
         // This address exists for use by messages coming from outside the system, in order to
@@ -73,38 +70,34 @@ impl Actor {
         // a different ID.
         let robust_address = rt.new_actor_address()?;
 
-        log::trace!("robust address: {:?}", &robust_address);
+        exec_at(rt, params.code_cid, params.constructor_params, robust_address)
+    }
 
-        // Allocate an ID for this actor.
-        // Store mapping of actor addresses to the actor ID.
-        let (id_address, existing): (ActorID, bool) = rt.transaction(|s: &mut State, rt| {
-            s.map_addresses_to_id(rt.store(), &robust_address, None)
-                .context("failed to allocate ID address")
-        })?;
+    /// Exec init actor at a robust address derived from the caller, the salt, the code and the
+    /// constructor parameters (see [`compute_deterministic_address`]) rather than the message.
+    ///
+    /// The caller is identified by its delegated address, or by its key address if it's an
+    /// account, so the address doesn't depend on the caller's ID. Other callers are forbidden.
+    pub fn exec_deterministic(
+        rt: &impl Runtime,
+        params: ExecDeterministicParams,
+    ) -> Result<ExecDeterministicReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
 
-        if existing {
-            // NOTE: this case should be impossible, but we check it anyways just in case something
-            // changes.
-            return Err(actor_error!(
-                forbidden,
-                "cannot exec over an existing actor {}",
-                id_address
-            ));
-        }
+        log::trace!("called exec_deterministic; params.code_cid: {:?}", &params.code_cid);
 
-        // Create an empty actor
-        rt.create_actor(params.code_cid, id_address, None)?;
+        check_can_exec(rt, &params.code_cid)?;
 
-        // Invoke constructor
-        extract_send_result(rt.send_simple(
-            &Address::new_id(id_address),
-            METHOD_CONSTRUCTOR,
-            params.constructor_params.into(),
-            rt.message().value_received(),
-        ))
-        .context("constructor failed")?;
+        let caller = resolve_stable_caller(rt)?;
+        let robust_address = compute_deterministic_address(
+            rt,
+            &caller,
+            &params.code_cid,
+            &params.salt,
+            &params.constructor_params,
+        );
 
-        Ok(ExecReturn { id_address: Address::new_id(id_address), robust_address })
+        exec_at(rt, params.code_cid, params.constructor_params, robust_address)
     }
 
     /// Exec4 init actor
@@ -162,6 +155,35 @@ impl Actor {
 
         Ok(Exec4Return { id_address: Address::new_id(id_address), robust_address })
     }
+
+    /// Resolves an address to the ID address it maps to. ID addresses resolve to themselves.
+    pub fn resolve_address(
+        rt: &impl Runtime,
+        params: ResolveAddressParams,
+    ) -> Result<ResolveAddressReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let state: State = rt.state()?;
+        let id_address = state.resolve_address(rt.store(), &params.address)?;
+        Ok(ResolveAddressReturn { id_address })
+    }
+
+    /// Looks up the delegated (f4) address of an actor, if it has one.
+    pub fn lookup_delegated_address(
+        rt: &impl Runtime,
+        params: LookupDelegatedAddressParams,
+    ) -> Result<LookupDelegatedAddressReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        Ok(LookupDelegatedAddressReturn {
+            delegated_address: rt.lookup_delegated_address(params.actor_id),
+        })
+    }
+
+    /// Returns the ID the next actor to be created will be assigned.
+    pub fn next_actor_id(rt: &impl Runtime) -> Result<NextActorIDReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let state: State = rt.state()?;
+        Ok(NextActorIDReturn { next_id: state.next_id })
+    }
 }
 
 impl ActorCode for Actor {
@@ -175,7 +197,124 @@ impl ActorCode for Actor {
         Constructor => constructor,
         Exec => exec,
         Exec4 => exec4,
+        ExecDeterministicExported => exec_deterministic,
+        ResolveAddressExported => resolve_address,
+        LookupDelegatedAddressExported => lookup_delegated_address,
+        NextActorIDExported => next_actor_id,
+    }
+}
+
+/// Domain separation tag for addresses derived by [`compute_deterministic_address`].
+const DETERMINISTIC_ADDRESS_TAG: &[u8] = b"fil/init/exec-deterministic";
+
+/// Account actor method returning the account's key address.
+const ACCOUNT_PUBKEY_ADDRESS_METHOD: u64 = 2;
+
+/// Computes the robust (f2) address an actor created by `ExecDeterministic` will be assigned.
+///
+/// The address only depends on the calling actor's stable address (see
+/// [`Actor::exec_deterministic`]), the salt, the actor code and the constructor parameters, so it
+/// can be computed before the actor exists and doesn't change if a re-org changes the caller's ID.
+pub fn compute_deterministic_address(
+    rt: &impl Runtime,
+    caller: &Address,
+    code_cid: &Cid,
+    salt: &[u8; 32],
+    constructor_params: &[u8],
+) -> Address {
+    let params_hash = rt.hash_blake2b(constructor_params);
+    Address::new_actor(
+        &[DETERMINISTIC_ADDRESS_TAG, &caller.to_bytes(), salt, &code_cid.to_bytes(), &params_hash]
+            .concat(),
+    )
+}
+
+/// Resolves the caller to an address that doesn't depend on its ID: its delegated (f4) address if
+/// it has one, or an account's key address. Other callers can't create actors deterministically.
+fn resolve_stable_caller(rt: &impl Runtime) -> Result<Address, ActorError> {
+    let caller = rt.message().caller();
+    let caller_id = caller.id().unwrap();
+    if let Some(delegated) = rt.lookup_delegated_address(caller_id) {
+        return Ok(delegated);
+    }
+    let caller_code = rt
+        .get_actor_code_cid(&caller_id)
+        .ok_or_else(|| actor_error!(illegal_state, "no code for caller as {}", caller))?;
+    match rt.resolve_builtin_actor_type(&caller_code) {
+        Some(Type::Account) => {
+            let ret = extract_send_result(rt.send(
+                &caller,
+                ACCOUNT_PUBKEY_ADDRESS_METHOD,
+                None,
+                TokenAmount::zero(),
+                None,
+                SendFlags::READ_ONLY,
+            ))
+            .context("account failed to return its key address")?;
+            deserialize_block(ret)
+        }
+        Some(t) => {
+            Err(actor_error!(forbidden; "caller of type {} has no stable address", t.name()))
+        }
+        None => {
+            Err(actor_error!(forbidden; "caller with code {} has no stable address", caller_code))
+        }
+    }
+}
+
+/// Checks the caller is allowed to exec an actor with the given code.
+fn check_can_exec(rt: &impl Runtime, code_cid: &Cid) -> Result<(), ActorError> {
+    let caller_code =
+        rt.get_actor_code_cid(&rt.message().caller().id().unwrap()).ok_or_else(|| {
+            actor_error!(illegal_state, "no code for caller as {}", rt.message().caller())
+        })?;
+
+    log::trace!("caller code CID: {:?}", &caller_code);
+
+    if !can_exec(rt, &caller_code, code_cid) {
+        return Err(actor_error!(forbidden;
+                "called type {} cannot exec actor type {}",
+                &caller_code, code_cid
+        ));
     }
+    Ok(())
+}
+
+/// Creates an actor at the given robust address and invokes its constructor.
+fn exec_at(
+    rt: &impl Runtime,
+    code_cid: Cid,
+    constructor_params: RawBytes,
+    robust_address: Address,
+) -> Result<ExecReturn, ActorError> {
+    log::trace!("robust address: {:?}", &robust_address);
+
+    // Allocate an ID for this actor.
+    // Store mapping of actor addresses to the actor ID.
+    let (id_address, existing): (ActorID, bool) = rt.transaction(|s: &mut State, rt| {
+        s.map_addresses_to_id(rt.store(), &robust_address, None)
+            .context("failed to allocate ID address")
+    })?;
+
+    if existing {
+        // NOTE: this can only happen when a deterministic address is reused, but we check it
+        // for all robust addresses just in case something changes.
+        return Err(actor_error!(forbidden, "cannot exec over an existing actor {}", id_address));
+    }
+
+    // Create an empty actor
+    rt.create_actor(code_cid, id_address, None)?;
+
+    // Invoke constructor
+    extract_send_result(rt.send_simple(
+        &Address::new_id(id_address),
+        METHOD_CONSTRUCTOR,
+        constructor_params.into(),
+        rt.message().value_received(),
+    ))
+    .context("constructor failed")?;
+
+    Ok(ExecReturn { id_address: Address::new_id(id_address), robust_address })
 }
 
 fn can_exec(rt: &impl Runtime, caller: &Cid, exec: &Cid) -> bool {
@@ -2,9 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 use cid::Cid;
+use fvm_ipld_encoding::strict_bytes;
 use fvm_ipld_encoding::tuple::*;
 use fvm_ipld_encoding::RawBytes;
 use fvm_shared::address::Address;
+use fvm_shared::ActorID;
 
 /// Init actor Constructor parameters
 #[derive(Serialize_tuple, Deserialize_tuple)]
@@ -28,6 +30,18 @@ pub struct ExecReturn {
     pub robust_address: Address,
 }
 
+/// Init actor ExecDeterministic Params
+#[derive(Serialize_tuple, Deserialize_tuple)]
+pub struct ExecDeterministicParams {
+    pub code_cid: Cid,
+    pub constructor_params: RawBytes,
+    #[serde(with = "strict_bytes")]
+    pub salt: [u8; 32],
+}
+
+/// Init actor ExecDeterministic Return value
+pub type ExecDeterministicReturn = ExecReturn;
+
 /// Init actor Exec4 Params
 #[derive(Serialize_tuple, Deserialize_tuple)]
 pub struct Exec4Params {
@@ -38,3 +52,40 @@ pub struct Exec4Params {
 
 /// Init actor Exec4 Return value
 pub type Exec4Return = ExecReturn;
+
+/// Init actor ResolveAddress Params
+#[derive(Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct ResolveAddressParams {
+    pub address: Address,
+}
+
+/// Init actor ResolveAddress Return value
+#[derive(Debug, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct ResolveAddressReturn {
+    /// ID based address the address maps to, if any
+    pub id_address: Option<Address>,
+}
+
+/// Init actor LookupDelegatedAddress Params
+#[derive(Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct LookupDelegatedAddressParams {
+    pub actor_id: ActorID,
+}
+
+/// Init actor LookupDelegatedAddress Return value
+#[derive(Debug, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct LookupDelegatedAddressReturn {
+    /// The actor's f4 address, if it has one
+    pub delegated_address: Option<Address>,
+}
+
+/// Init actor NextActorID Return value
+#[derive(Debug, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct NextActorIDReturn {
+    pub next_id: ActorID,
+}
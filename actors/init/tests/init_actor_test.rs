@@ -2,12 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 use std::cell::RefCell;
+use std::str::FromStr;
 
 use cid::Cid;
+use fil_actor_init::compute_deterministic_address;
 use fil_actor_init::testing::check_state_invariants;
 use fil_actor_init::{
-    Actor as InitActor, ConstructorParams, Exec4Params, Exec4Return, ExecParams, ExecReturn,
-    Method, State,
+    Actor as InitActor, ConstructorParams, Exec4Params, Exec4Return, ExecDeterministicParams,
+    ExecParams, ExecReturn, LookupDelegatedAddressParams, LookupDelegatedAddressReturn, Method,
+    NextActorIDReturn, ResolveAddressParams, ResolveAddressReturn, State,
 };
 use fil_actors_runtime::runtime::Runtime;
 use fil_actors_runtime::{test_utils::*, EAM_ACTOR_ADDR, EAM_ACTOR_ID};
@@ -19,6 +22,7 @@ use fvm_ipld_encoding::RawBytes;
 use fvm_shared::address::Address;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
+use fvm_shared::sys::SendFlags;
 use fvm_shared::{ActorID, HAMT_BIT_WIDTH, METHOD_CONSTRUCTOR};
 use num_traits::Zero;
 use serde::Serialize;
@@ -242,6 +246,180 @@ fn create_multisig_actor() {
     check_state(&rt);
 }
 
+#[test]
+fn create_multisig_actor_deterministic() {
+    let rt = construct_runtime();
+    construct_and_verify(&rt);
+
+    // Account actor creating multisig actor
+    let some_acc_actor = Address::new_id(1234);
+    let key_address = Address::new_secp256k1(&[3; 65]).unwrap();
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, some_acc_actor);
+
+    let fake_params = ConstructorParams { network_name: String::from("fake_param") };
+    let constructor_params = RawBytes::serialize(&fake_params).unwrap();
+    let salt = [7; 32];
+
+    // The robust address can be computed before the actor is created.
+    let robust_address = compute_deterministic_address(
+        &rt,
+        &key_address,
+        &MULTISIG_ACTOR_CODE_ID,
+        &salt,
+        &constructor_params,
+    );
+
+    // The address is derived from the account's key address rather than its ID.
+    expect_pubkey_address(&rt, some_acc_actor, key_address);
+
+    // Next id
+    let expected_id = 100;
+    let expected_id_addr = Address::new_id(expected_id);
+    rt.expect_create_actor(*MULTISIG_ACTOR_CODE_ID, expected_id, None);
+
+    // Expect a send to the multisig actor constructor
+    rt.expect_send_simple(
+        expected_id_addr,
+        METHOD_CONSTRUCTOR,
+        IpldBlock::serialize_cbor(&fake_params).unwrap(),
+        TokenAmount::zero(),
+        None,
+        ExitCode::OK,
+    );
+
+    let params = ExecDeterministicParams {
+        code_cid: *MULTISIG_ACTOR_CODE_ID,
+        constructor_params: constructor_params.clone(),
+        salt,
+    };
+    let exec_deterministic = || -> Result<ExecReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let ret = rt.call::<InitActor>(
+            Method::ExecDeterministicExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        );
+        rt.verify();
+        check_state(&rt);
+        ret.and_then(|v| v.unwrap().deserialize().map_err(|e| e.into()))
+    };
+
+    let exec_ret = exec_deterministic().unwrap();
+    assert_eq!(robust_address, exec_ret.robust_address, "Robust address does not match");
+    assert_eq!(expected_id_addr, exec_ret.id_address, "Id address does not match");
+
+    let state: State = rt.get_state();
+    let resolved = state.resolve_address(rt.store(), &robust_address).unwrap();
+    assert_eq!(Some(expected_id_addr), resolved);
+
+    // The same salt and parameters can't be used again.
+    expect_pubkey_address(&rt, some_acc_actor, key_address);
+    let err = exec_deterministic().unwrap_err();
+    assert_eq!(err.exit_code(), ExitCode::USR_FORBIDDEN);
+
+    // A different salt gives a different address.
+    let other = compute_deterministic_address(
+        &rt,
+        &key_address,
+        &MULTISIG_ACTOR_CODE_ID,
+        &[8; 32],
+        &constructor_params,
+    );
+    assert_ne!(robust_address, other);
+}
+
+#[test]
+fn create_actor_deterministic_from_delegated_caller() {
+    let rt = construct_runtime();
+    construct_and_verify(&rt);
+
+    // A caller with a delegated address uses it, without calling the caller.
+    let caller = Address::new_id(1234);
+    let delegated = Address::new_delegated(EAM_ACTOR_ID, &[0xaa; 20]).unwrap();
+    rt.set_caller(*EVM_ACTOR_CODE_ID, caller);
+    rt.set_delegated_address(1234, delegated);
+
+    let fake_params = ConstructorParams { network_name: String::from("fake_param") };
+    let constructor_params = RawBytes::serialize(&fake_params).unwrap();
+    let salt = [7; 32];
+    let robust_address = compute_deterministic_address(
+        &rt,
+        &delegated,
+        &MULTISIG_ACTOR_CODE_ID,
+        &salt,
+        &constructor_params,
+    );
+
+    let expected_id_addr = Address::new_id(100);
+    rt.expect_create_actor(*MULTISIG_ACTOR_CODE_ID, 100, None);
+    rt.expect_send_simple(
+        expected_id_addr,
+        METHOD_CONSTRUCTOR,
+        IpldBlock::serialize_cbor(&fake_params).unwrap(),
+        TokenAmount::zero(),
+        None,
+        ExitCode::OK,
+    );
+    rt.expect_validate_caller_any();
+    let params =
+        ExecDeterministicParams { code_cid: *MULTISIG_ACTOR_CODE_ID, constructor_params, salt };
+    let exec_ret: ExecReturn = rt
+        .call::<InitActor>(
+            Method::ExecDeterministicExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        )
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+    check_state(&rt);
+    assert_eq!(robust_address, exec_ret.robust_address);
+    assert_eq!(expected_id_addr, exec_ret.id_address);
+}
+
+#[test]
+fn create_actor_deterministic_requires_stable_caller() {
+    let rt = construct_runtime();
+    construct_and_verify(&rt);
+
+    // A multisig only has its ID and an f2 address that can't be looked up.
+    rt.set_caller(*MULTISIG_ACTOR_CODE_ID, Address::new_id(1234));
+    rt.expect_validate_caller_any();
+    let params = ExecDeterministicParams {
+        code_cid: *MULTISIG_ACTOR_CODE_ID,
+        constructor_params: RawBytes::serialize(ConstructorParams {
+            network_name: String::from("fake_param"),
+        })
+        .unwrap(),
+        salt: [7; 32],
+    };
+    expect_abort(
+        ExitCode::USR_FORBIDDEN,
+        rt.call::<InitActor>(
+            Method::ExecDeterministicExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        ),
+    );
+    rt.verify();
+    check_state(&rt);
+}
+
+#[test]
+fn deterministic_address_vector() {
+    let rt = construct_runtime();
+    let caller = Address::new_secp256k1(&[3; 65]).unwrap();
+    let address = compute_deterministic_address(
+        &rt,
+        &caller,
+        &MULTISIG_ACTOR_CODE_ID,
+        &[7; 32],
+        &[0x81, 0x60],
+    );
+    // blake2b-160 of the tag, the caller's address bytes, the salt, the code CID bytes and the
+    // blake2b-256 of the constructor params, computed independently.
+    assert_eq!(address, Address::from_str("f2c3ydt6lqynxetnrlivle3hyuf2kxvcdacuchd3q").unwrap());
+}
+
 #[test]
 fn sending_constructor_failure() {
     let rt = construct_runtime();
@@ -411,6 +589,71 @@ fn call_exec4_placeholder() {
     assert_eq!(expected_id_addr, resolved_id, "f4 address not assigned to the right actor");
 }
 
+#[test]
+fn query_addresses() {
+    let rt = construct_runtime();
+    construct_and_verify(&rt);
+
+    // Register an f4 address with the init actor.
+    let f4_addr = Address::new_delegated(EAM_ACTOR_ID, b"foobar").unwrap();
+    let id = {
+        let mut state: State = rt.get_state();
+        let (id, _) = state.map_addresses_to_id(rt.store(), &f4_addr, None).unwrap();
+        rt.replace_state(&state);
+        id
+    };
+    rt.set_delegated_address(id, f4_addr);
+
+    // Any caller can query addresses.
+    rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+
+    let resolve = |address: Address| -> ResolveAddressReturn {
+        rt.expect_validate_caller_any();
+        let ret = rt
+            .call::<InitActor>(
+                Method::ResolveAddressExported as u64,
+                IpldBlock::serialize_cbor(&ResolveAddressParams { address }).unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        ret
+    };
+    assert_eq!(Some(Address::new_id(id)), resolve(f4_addr).id_address);
+    assert_eq!(Some(Address::new_id(id)), resolve(Address::new_id(id)).id_address);
+    assert_eq!(None, resolve(Address::new_actor(b"unknown")).id_address);
+
+    let lookup = |actor_id: ActorID| -> LookupDelegatedAddressReturn {
+        rt.expect_validate_caller_any();
+        let ret = rt
+            .call::<InitActor>(
+                Method::LookupDelegatedAddressExported as u64,
+                IpldBlock::serialize_cbor(&LookupDelegatedAddressParams { actor_id }).unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        ret
+    };
+    assert_eq!(Some(f4_addr), lookup(id).delegated_address);
+    assert_eq!(None, lookup(id + 1).delegated_address);
+
+    rt.expect_validate_caller_any();
+    let ret: NextActorIDReturn = rt
+        .call::<InitActor>(Method::NextActorIDExported as u64, None)
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+    assert_eq!(id + 1, ret.next_id);
+    check_state(&rt);
+}
+
 fn construct_and_verify(rt: &MockRuntime) {
     rt.set_caller(*SYSTEM_ACTOR_CODE_ID, SYSTEM_ACTOR_ADDR);
     rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
@@ -435,6 +678,23 @@ fn construct_and_verify(rt: &MockRuntime) {
     check_state(rt);
 }
 
+const PUBKEY_ADDRESS_METHOD: u64 = 2;
+
+/// Expects the init actor to ask an account for its key address.
+fn expect_pubkey_address(rt: &MockRuntime, account: Address, key_address: Address) {
+    rt.expect_send(
+        account,
+        PUBKEY_ADDRESS_METHOD,
+        None,
+        TokenAmount::zero(),
+        None,
+        SendFlags::READ_ONLY,
+        IpldBlock::serialize_cbor(&key_address).unwrap(),
+        ExitCode::OK,
+        None,
+    );
+}
+
 fn exec_and_verify<S: Serialize>(
     rt: &MockRuntime,
     code_id: Cid,
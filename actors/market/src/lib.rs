@@ -54,6 +54,7 @@ pub mod policy;
 pub mod testing;
 
 mod deal;
+mod emit;
 mod state;
 mod types;
 
@@ -484,6 +485,13 @@ impl Actor {
             .with_context_code(ExitCode::USR_ILLEGAL_ARGUMENT, || {
                 format!("failed to notify deal with proposal cid {}", valid_deal.cid)
             })?;
+
+            emit::deal_published(
+                rt,
+                new_deal_ids[i],
+                valid_deal.proposal.client.id().unwrap(),
+                provider_id,
+            )?;
         }
 
         Ok(PublishStorageDealsReturn { ids: new_deal_ids, valid_deals: valid_input_bf })
@@ -539,7 +547,7 @@ impl Actor {
         let miner_addr = rt.message().caller();
         let curr_epoch = rt.curr_epoch();
 
-        let (deal_spaces, verified_infos) = rt.transaction(|st: &mut State, rt| {
+        let (deal_spaces, verified_infos, activated) = rt.transaction(|st: &mut State, rt| {
             let proposal_array = st.get_proposal_array(rt.store())?;
             let proposals = get_proposals(&proposal_array, &params.deal_ids, st.next_id)?;
 
@@ -557,6 +565,7 @@ impl Actor {
             // Update deal states
             let mut verified_infos = Vec::new();
             let mut deal_states: Vec<(DealID, DealState)> = vec![];
+            let mut activated: Vec<(DealID, ActorID, ActorID)> = vec![];
 
             for (deal_id, proposal) in proposals {
                 // This construction could be replaced with a single "update deal state"
@@ -609,13 +618,22 @@ impl Actor {
                         verified_claim: allocation,
                     },
                 ));
+                activated.push((
+                    deal_id,
+                    proposal.client.id().unwrap(),
+                    proposal.provider.id().unwrap(),
+                ));
             }
 
             st.put_deal_states(rt.store(), &deal_states)?;
 
-            Ok((deal_spaces, verified_infos))
+            Ok((deal_spaces, verified_infos, activated))
         })?;
 
+        for (deal_id, client, provider) in activated {
+            emit::deal_activated(rt, deal_id, client, provider)?;
+        }
+
         Ok(ActivateDealsResult { nonverified_deal_space: deal_spaces.deal_space, verified_infos })
     }
 
@@ -629,8 +647,9 @@ impl Actor {
         rt.validate_immediate_caller_type(std::iter::once(&Type::Miner))?;
         let miner_addr = rt.message().caller();
 
-        rt.transaction(|st: &mut State, rt| {
+        let terminated = rt.transaction(|st: &mut State, rt| {
             let mut deal_states: Vec<(DealID, DealState)> = vec![];
+            let mut terminated: Vec<(DealID, ActorID, ActorID)> = vec![];
 
             for id in params.deal_ids {
                 let deal = st.find_proposal(rt.store(), id)?;
@@ -676,11 +695,16 @@ impl Actor {
                 state.slash_epoch = params.epoch;
 
                 deal_states.push((id, state));
+                terminated.push((id, deal.client.id().unwrap(), deal.provider.id().unwrap()));
             }
 
             st.put_deal_states(rt.store(), &deal_states)?;
-            Ok(())
+            Ok(terminated)
         })?;
+
+        for (deal_id, client, provider) in terminated {
+            emit::deal_terminated(rt, deal_id, client, provider)?;
+        }
         Ok(())
     }
 
@@ -708,10 +732,12 @@ impl Actor {
         let mut amount_slashed = TokenAmount::zero();
         let curr_epoch = rt.curr_epoch();
 
-        rt.transaction(|st: &mut State, rt| {
+        let (timed_out, completed) = rt.transaction(|st: &mut State, rt| {
             let last_cron = st.last_cron;
             let mut new_updates_scheduled: BTreeMap<ChainEpoch, Vec<DealID>> = BTreeMap::new();
             let mut epochs_completed: Vec<ChainEpoch> = vec![];
+            let mut timed_out: Vec<(DealID, ActorID, ActorID)> = vec![];
+            let mut completed: Vec<(DealID, ActorID, ActorID)> = vec![];
 
             for i in (last_cron + 1)..=rt.curr_epoch() {
                 let deal_ids = st.get_deals_for_epoch(rt.store(), i)?;
@@ -763,6 +789,11 @@ impl Actor {
                         // Delete pending deal allocation id (if present).
                         st.remove_pending_deal_allocation_id(rt.store(), &deal_id_key(deal_id))?;
 
+                        timed_out.push((
+                            deal_id,
+                            deal.client.id().unwrap(),
+                            deal.provider.id().unwrap(),
+                        ));
                         continue;
                     }
                     let mut state = state.unwrap();
@@ -808,6 +839,15 @@ impl Actor {
                                 "failed to delete deal proposal: does not exist"
                             ));
                         }
+
+                        // Slashed deals were reported as terminated when the slash was recorded.
+                        if state.slash_epoch == EPOCH_UNDEFINED {
+                            completed.push((
+                                deal_id,
+                                deal.client.id().unwrap(),
+                                deal.provider.id().unwrap(),
+                            ));
+                        }
                     } else {
                         if !slash_amount.is_zero() {
                             return Err(actor_error!(
@@ -838,9 +878,16 @@ impl Actor {
             st.remove_deals_by_epoch(rt.store(), &epochs_completed)?;
             st.put_batch_deals_by_epoch(rt.store(), &new_updates_scheduled)?;
             st.last_cron = rt.curr_epoch();
-            Ok(())
+            Ok((timed_out, completed))
         })?;
 
+        for (deal_id, client, provider) in timed_out {
+            emit::deal_terminated(rt, deal_id, client, provider)?;
+        }
+        for (deal_id, client, provider) in completed {
+            emit::deal_completed(rt, deal_id, client, provider)?;
+        }
+
         if !amount_slashed.is_zero() {
             extract_send_result(rt.send_simple(
                 &BURNT_FUNDS_ACTOR_ADDR,
@@ -50,7 +50,16 @@ fn timed_out_deal_is_slashed_and_deleted() {
         None,
         ExitCode::OK,
     );
-    cron_tick(&rt);
+    // The deal is reported as terminated.
+    expect_emitted(
+        &rt,
+        "deal-terminated",
+        deal_id,
+        CLIENT_ADDR.id().unwrap(),
+        PROVIDER_ADDR.id().unwrap(),
+    );
+    assert!(cron_tick_raw(&rt).unwrap().is_none());
+    rt.verify();
 
     let client_acct = get_balance(&rt, &CLIENT_ADDR);
     assert_eq!(c_escrow, client_acct.balance);
@@ -28,7 +28,7 @@ use fil_actors_runtime::{
     network::EPOCHS_IN_DAY,
     runtime::{builtins::Type, Policy, Runtime},
     test_utils::*,
-    ActorError, BatchReturn, SetMultimap, BURNT_FUNDS_ACTOR_ADDR, CRON_ACTOR_ADDR,
+    ActorError, BatchReturn, EventBuilder, SetMultimap, BURNT_FUNDS_ACTOR_ADDR, CRON_ACTOR_ADDR,
     DATACAP_TOKEN_ACTOR_ADDR, REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR,
     STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
 };
@@ -303,6 +303,17 @@ pub fn activate_deals(
     current_epoch: ChainEpoch,
     deal_ids: &[DealID],
 ) -> ActivateDealsResult {
+    for deal_id in deal_ids {
+        let p = get_deal_proposal(rt, *deal_id);
+        expect_emitted(
+            rt,
+            "deal-activated",
+            *deal_id,
+            p.client.id().unwrap(),
+            p.provider.id().unwrap(),
+        );
+    }
+
     let ret = activate_deals_raw(rt, sector_expiry, provider, current_epoch, deal_ids).unwrap();
     ret.unwrap().deserialize().expect("VerifyDealsForActivation failed!")
 }
@@ -623,6 +634,13 @@ pub fn publish_deals(
             None,
             ExitCode::OK,
         );
+        expect_emitted(
+            rt,
+            "deal-published",
+            deal_id,
+            deal.client.id().unwrap(),
+            deal.provider.id().unwrap(),
+        );
         deal_id += 1;
     }
 
@@ -711,10 +729,68 @@ pub fn assert_deals_not_activated(rt: &MockRuntime, _epoch: ChainEpoch, deal_ids
 }
 
 pub fn cron_tick(rt: &MockRuntime) {
+    expect_cron_events(rt);
     assert!(cron_tick_raw(rt).unwrap().is_none());
     rt.verify()
 }
 
+/// Expects the events of a cron tick at the current epoch: a deal-terminated event for each deal
+/// that timed out before activation, then a deal-completed event for each active, unslashed deal
+/// processed at or after its end epoch.
+fn expect_cron_events(rt: &MockRuntime) {
+    let st: State = rt.get_state();
+    let curr_epoch = *rt.epoch.borrow();
+    let mut timed_out = vec![];
+    let mut completed = vec![];
+    for epoch in (st.last_cron + 1)..=curr_epoch {
+        for deal_id in st.get_deals_for_epoch(&rt.store, epoch).unwrap() {
+            let proposal = match st.find_proposal(&rt.store, deal_id).unwrap() {
+                Some(proposal) => proposal,
+                None => continue,
+            };
+            match st.find_deal_state(&rt.store, deal_id).unwrap() {
+                None => timed_out.push((deal_id, proposal)),
+                Some(state)
+                    if state.slash_epoch == EPOCH_UNDEFINED && curr_epoch >= proposal.end_epoch =>
+                {
+                    completed.push((deal_id, proposal))
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    for (typ, deals) in [("deal-terminated", timed_out), ("deal-completed", completed)] {
+        for (deal_id, proposal) in deals {
+            expect_emitted(
+                rt,
+                typ,
+                deal_id,
+                proposal.client.id().unwrap(),
+                proposal.provider.id().unwrap(),
+            );
+        }
+    }
+}
+
+/// Expects a market deal event carrying the deal ID and its parties.
+pub fn expect_emitted(
+    rt: &MockRuntime,
+    typ: &str,
+    deal_id: DealID,
+    client: ActorID,
+    provider: ActorID,
+) {
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ(typ)
+            .field_indexed("id", &deal_id)
+            .field_indexed("client", &client)
+            .field_indexed("provider", &provider)
+            .build()
+            .unwrap(),
+    );
+}
+
 pub fn cron_tick_raw(rt: &MockRuntime) -> Result<Option<IpldBlock>, ActorError> {
     rt.expect_validate_caller_addr(vec![CRON_ACTOR_ADDR]);
     rt.set_caller(*CRON_ACTOR_CODE_ID, CRON_ACTOR_ADDR);
@@ -1059,6 +1135,26 @@ pub fn generate_deal_proposal(
 }
 
 pub fn terminate_deals(rt: &MockRuntime, miner_addr: Address, deal_ids: &[DealID]) {
+    // Only active deals that have not yet expired or been slashed are terminated.
+    let st: State = rt.get_state();
+    let epoch = *rt.epoch.borrow();
+    for deal_id in deal_ids {
+        let proposal = match st.find_proposal(&rt.store, *deal_id).unwrap() {
+            Some(proposal) if proposal.end_epoch > epoch => proposal,
+            _ => continue,
+        };
+        match st.find_deal_state(&rt.store, *deal_id).unwrap() {
+            Some(state) if state.slash_epoch == EPOCH_UNDEFINED => expect_emitted(
+                rt,
+                "deal-terminated",
+                *deal_id,
+                proposal.client.id().unwrap(),
+                proposal.provider.id().unwrap(),
+            ),
+            _ => continue,
+        }
+    }
+
     let ret = terminate_deals_raw(rt, miner_addr, deal_ids).unwrap();
     assert!(ret.is_none());
     rt.verify();
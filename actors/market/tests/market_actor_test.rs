@@ -935,6 +935,13 @@ fn provider_and_client_addresses_are_resolved_before_persisting_state_and_sent_t
         None,
         ExitCode::OK,
     );
+    expect_emitted(
+        &rt,
+        "deal-published",
+        deal_id,
+        client_resolved.id().unwrap(),
+        provider_resolved.id().unwrap(),
+    );
 
     let ret: PublishStorageDealsReturn = rt
         .call::<MarketActor>(
@@ -2107,6 +2114,13 @@ fn insufficient_client_balance_in_a_batch() {
         None,
         ExitCode::OK,
     );
+    expect_emitted(
+        &rt,
+        "deal-published",
+        next_deal_id,
+        deal2.client.id().unwrap(),
+        deal2.provider.id().unwrap(),
+    );
 
     rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, WORKER_ADDR);
 
@@ -2247,6 +2261,13 @@ fn insufficient_provider_balance_in_a_batch() {
         None,
         ExitCode::OK,
     );
+    expect_emitted(
+        &rt,
+        "deal-published",
+        next_deal_id,
+        deal2.client.id().unwrap(),
+        deal2.provider.id().unwrap(),
+    );
 
     rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, WORKER_ADDR);
 
@@ -2390,6 +2411,13 @@ fn psd_restricted_correctly() {
         None,
         ExitCode::OK,
     );
+    expect_emitted(
+        &rt,
+        "deal-published",
+        next_deal_id,
+        deal.client.id().unwrap(),
+        deal.provider.id().unwrap(),
+    );
 
     let ret: PublishStorageDealsReturn = rt
         .call::<MarketActor>(
@@ -375,6 +375,13 @@ fn fail_when_deals_have_different_providers() {
         None,
         ExitCode::OK,
     );
+    expect_emitted(
+        &rt,
+        "deal-published",
+        next_deal_id,
+        deal1.client.id().unwrap(),
+        deal1.provider.id().unwrap(),
+    );
 
     let psd_ret: PublishStorageDealsReturn = rt
         .call::<MarketActor>(
@@ -0,0 +1,92 @@
+use cid::Cid;
+use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::{ActorError, EventBuilder};
+use fvm_shared::deal::DealID;
+use fvm_shared::sector::SectorNumber;
+
+/// Indicates a sector has been pre-committed, with the deals it is to be activated with.
+/// The unsealed CID is absent for a committed-capacity sector.
+pub fn sector_precommitted(
+    rt: &impl Runtime,
+    sector: SectorNumber,
+    unsealed_cid: Option<Cid>,
+    deals: &[DealID],
+) -> Result<(), ActorError> {
+    rt.emit_event(
+        &EventBuilder::new()
+            .typ("sector-precommitted")
+            .with_sector_data(sector, unsealed_cid, deals)
+            .build()?,
+    )
+}
+
+/// Indicates a sector has been activated, with the deals it was activated with.
+/// The unsealed CID is absent for a committed-capacity sector.
+pub fn sector_activated(
+    rt: &impl Runtime,
+    sector: SectorNumber,
+    unsealed_cid: Option<Cid>,
+    deals: &[DealID],
+) -> Result<(), ActorError> {
+    rt.emit_event(
+        &EventBuilder::new()
+            .typ("sector-activated")
+            .with_sector_data(sector, unsealed_cid, deals)
+            .build()?,
+    )
+}
+
+/// Indicates a sector's replica has been updated to hold the given deals.
+pub fn sector_updated(
+    rt: &impl Runtime,
+    sector: SectorNumber,
+    unsealed_cid: Option<Cid>,
+    deals: &[DealID],
+) -> Result<(), ActorError> {
+    rt.emit_event(
+        &EventBuilder::new()
+            .typ("sector-updated")
+            .with_sector_data(sector, unsealed_cid, deals)
+            .build()?,
+    )
+}
+
+/// Indicates a sector has been terminated, either explicitly, by being faulty for too long,
+/// or by reaching its expiration, with the deals it held at the time.
+/// The unsealed CID is absent for a committed-capacity sector, or one activated before it was
+/// recorded.
+pub fn sector_terminated(
+    rt: &impl Runtime,
+    sector: SectorNumber,
+    unsealed_cid: Option<Cid>,
+    deals: &[DealID],
+) -> Result<(), ActorError> {
+    rt.emit_event(
+        &EventBuilder::new()
+            .typ("sector-terminated")
+            .with_sector_data(sector, unsealed_cid, deals)
+            .build()?,
+    )
+}
+
+trait WithSectorData {
+    fn with_sector_data(
+        self,
+        sector: SectorNumber,
+        unsealed_cid: Option<Cid>,
+        deals: &[DealID],
+    ) -> EventBuilder;
+}
+
+impl WithSectorData for EventBuilder {
+    fn with_sector_data(
+        self,
+        sector: SectorNumber,
+        unsealed_cid: Option<Cid>,
+        deals: &[DealID],
+    ) -> EventBuilder {
+        self.field_indexed("sector", &sector)
+            .field_indexed("unsealed-cid", &unsealed_cid)
+            .field("deals", deals)
+    }
+}
@@ -74,6 +74,7 @@ mod deadline_assignment;
 mod deadline_info;
 mod deadline_state;
 mod deadlines;
+mod emit;
 mod expiration_queue;
 #[doc(hidden)]
 pub mod ext;
@@ -1300,6 +1301,7 @@ impl Actor {
                     // Skip checking if CID is defined because it cannot be so in Rust
 
                     new_sector_info.deal_ids = with_details.update.deals.clone();
+                    new_sector_info.unsealed_cid = Some(with_details.full_unsealed_cid);
                     new_sector_info.power_base_epoch = rt.curr_epoch();
 
                     let duration = new_sector_info.expiration - new_sector_info.power_base_epoch;
@@ -1482,6 +1484,17 @@ impl Actor {
         notify_pledge_changed(rt, &pledge_delta)?;
         request_update_power(rt, power_delta)?;
 
+        for dl_idx in deadlines_to_load.iter() {
+            for with_details in &decls_by_deadline[dl_idx] {
+                emit::sector_updated(
+                    rt,
+                    with_details.update.sector_number,
+                    Some(with_details.full_unsealed_cid),
+                    &with_details.update.deals,
+                )?;
+            }
+        }
+
         Ok(succeeded_sectors)
     }
 
@@ -1882,6 +1895,7 @@ impl Actor {
         }
         let mut fee_to_burn = TokenAmount::zero();
         let mut needs_cron = false;
+        let mut precommitted = Vec::with_capacity(sectors.len());
         rt.transaction(|state: &mut State, rt| {
             // Aggregate fee applies only when batching.
             if sectors.len() > 1 {
@@ -2007,6 +2021,7 @@ impl Actor {
                 .map_err(|e|
                     e.wrap("failed to allocate sector numbers")
                 )?;
+            precommitted.extend(chain_infos.iter().map(|c| c.info.clone()));
             state.put_precommitted_sectors(store, chain_infos)
                 .map_err(|e|
                     e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to write pre-committed sectors")
@@ -2031,6 +2046,10 @@ impl Actor {
                 CronEventPayload { event_type: CRON_EVENT_PROVING_DEADLINE },
             )?;
         }
+        precommitted.sort_by_key(|info| info.sector_number);
+        for info in precommitted {
+            emit::sector_precommitted(rt, info.sector_number, info.unsealed_cid.0, &info.deal_ids)?;
+        }
         Ok(())
     }
 
@@ -3924,8 +3943,8 @@ fn process_early_terminations(
     reward_smoothed: &FilterEstimate,
     quality_adj_power_smoothed: &FilterEstimate,
 ) -> Result</* more */ bool, ActorError> {
-    let (result, more, deals_to_terminate, penalty, pledge_delta) =
-        rt.transaction(|state: &mut State, rt| {
+    let (result, more, deals_to_terminate, terminated_sectors, penalty, pledge_delta) = rt
+        .transaction(|state: &mut State, rt| {
             let store = rt.store();
             let policy = rt.policy();
 
@@ -3948,7 +3967,14 @@ fn process_early_terminations(
             // before the cron callback fires.
             if result.is_empty() {
                 info!("no early terminations (maybe cron callback hasn't happened yet?)");
-                return Ok((result, more, Vec::new(), TokenAmount::zero(), TokenAmount::zero()));
+                return Ok((
+                    result,
+                    more,
+                    Vec::new(),
+                    Vec::new(),
+                    TokenAmount::zero(),
+                    TokenAmount::zero(),
+                ));
             }
 
             let info = get_miner_info(rt.store(), state)?;
@@ -3961,6 +3987,7 @@ fn process_early_terminations(
                 Vec::<ext::market::OnMinerSectorsTerminateParams>::with_capacity(
                     result.sectors.len(),
                 );
+            let mut terminated_sectors = Vec::<SectorOnChainInfo>::new();
             let mut penalty = TokenAmount::zero();
 
             for (epoch, sector_numbers) in result.iter() {
@@ -3979,8 +4006,9 @@ fn process_early_terminations(
                 // estimate ~one deal per sector.
                 let mut deal_ids = Vec::<DealID>::with_capacity(sectors.len());
                 for sector in sectors {
-                    deal_ids.extend(sector.deal_ids);
-                    total_initial_pledge += sector.initial_pledge;
+                    deal_ids.extend(&sector.deal_ids);
+                    total_initial_pledge += &sector.initial_pledge;
+                    terminated_sectors.push(sector);
                 }
 
                 let params = ext::market::OnMinerSectorsTerminateParams { epoch, deal_ids };
@@ -4012,7 +4040,7 @@ fn process_early_terminations(
             penalty = &penalty_from_vesting + penalty_from_balance;
             pledge_delta -= penalty_from_vesting;
 
-            Ok((result, more, deals_to_terminate, penalty, pledge_delta))
+            Ok((result, more, deals_to_terminate, terminated_sectors, penalty, pledge_delta))
         })?;
 
     // We didn't do anything, abort.
@@ -4037,6 +4065,10 @@ fn process_early_terminations(
         request_terminate_deals(rt, params.epoch, params.deal_ids)?;
     }
 
+    for sector in terminated_sectors {
+        emit::sector_terminated(rt, sector.sector_number, sector.unsealed_cid, &sector.deal_ids)?;
+    }
+
     // reschedule cron worker, if necessary.
     Ok(more)
 }
@@ -4055,6 +4087,7 @@ fn handle_proving_deadline(
     let mut penalty_total = TokenAmount::zero();
     let mut pledge_delta_total = TokenAmount::zero();
     let mut continue_cron = false;
+    let mut expired_sectors = Vec::<SectorOnChainInfo>::new();
 
     let state: State = rt.transaction(|state: &mut State, rt| {
         let policy = rt.policy();
@@ -4107,6 +4140,10 @@ fn handle_proving_deadline(
 
         power_delta_total += &result.power_delta;
         pledge_delta_total += &result.pledge_delta;
+        expired_sectors =
+            state.load_sector_infos(rt.store(), &result.on_time_expired_sectors).map_err(|e| {
+                e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load expired sectors")
+            })?;
 
         state
             .apply_penalty(&penalty_target)
@@ -4156,6 +4193,10 @@ fn handle_proving_deadline(
         info!("miner {} going inactive, deadline cron discontinued", rt.message().receiver())
     }
 
+    for sector in expired_sectors {
+        emit::sector_terminated(rt, sector.sector_number, sector.unsealed_cid, &sector.deal_ids)?;
+    }
+
     // Record whether or not we _have_ early terminations now.
     let has_early_terminations = have_pending_early_terminations(&state);
 
@@ -4813,11 +4854,12 @@ fn confirm_sector_proofs_valid_internal(
     let activated_sectors =
         batch_activate_deals_and_claim_allocations(rt, &deals_activation_infos)?;
 
-    let (total_pledge, newly_vested) = rt.transaction(|state: &mut State, rt| {
+    let (total_pledge, newly_vested, activated) = rt.transaction(|state: &mut State, rt| {
         let policy = rt.policy();
         let store = rt.store();
         let info = get_miner_info(store, state)?;
 
+        let mut activated = Vec::<&SectorPreCommitInfo>::with_capacity(activated_sectors.len());
         let mut new_sector_numbers = Vec::<SectorNumber>::with_capacity(activated_sectors.len());
         let mut deposit_to_unlock = TokenAmount::zero();
         let mut new_sectors = Vec::<SectorOnChainInfo>::new();
@@ -4896,8 +4938,10 @@ fn confirm_sector_proofs_valid_internal(
                 replaced_day_reward: TokenAmount::zero(),
                 sector_key_cid: None,
                 simple_qa_power: true,
+                unsealed_cid: pre_commit.info.unsealed_cid.0,
             };
 
+            activated.push(&pre_commit.info);
             new_sector_numbers.push(new_sector_info.sector_number);
             new_sectors.push(new_sector_info);
         }
@@ -4951,12 +4995,16 @@ fn confirm_sector_proofs_valid_internal(
 
         state.check_balance_invariants(&rt.current_balance()).map_err(balance_invariants_broken)?;
 
-        Ok((total_pledge, newly_vested))
+        Ok((total_pledge, newly_vested, activated))
     })?;
 
     // Request pledge update for activated sector.
     notify_pledge_changed(rt, &(total_pledge - newly_vested))?;
 
+    for info in activated {
+        emit::sector_activated(rt, info.sector_number, info.unsealed_cid.0, &info.deal_ids)?;
+    }
+
     Ok(())
 }
 
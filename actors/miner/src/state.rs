@@ -1102,6 +1102,7 @@ impl State {
                 previously_faulty_power: PowerPair::zero(),
                 detected_faulty_power: PowerPair::zero(),
                 total_faulty_power: PowerPair::zero(),
+                on_time_expired_sectors: BitField::new(),
             });
         }
 
@@ -1123,6 +1124,7 @@ impl State {
                 previously_faulty_power,
                 detected_faulty_power: PowerPair::zero(),
                 total_faulty_power: deadline.faulty_power,
+                on_time_expired_sectors: BitField::new(),
             });
         }
 
@@ -1166,6 +1168,7 @@ impl State {
             previously_faulty_power,
             detected_faulty_power,
             total_faulty_power,
+            on_time_expired_sectors: expired.on_time_sectors,
         })
     }
 
@@ -1203,6 +1206,9 @@ pub struct AdvanceDeadlineResult {
     /// Note that failed recovery power is included in both PreviouslyFaultyPower and
     /// DetectedFaultyPower, so TotalFaultyPower is not simply their sum.
     pub total_faulty_power: PowerPair,
+    /// Sectors that expired on time at the end of their committed life.
+    /// Sectors expiring early are instead added to the early terminations queue.
+    pub on_time_expired_sectors: BitField,
 }
 
 /// Static information about miner
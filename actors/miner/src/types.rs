@@ -16,6 +16,9 @@ use fvm_shared::sector::{
     SectorSize, StoragePower,
 };
 use fvm_shared::smooth::FilterEstimate;
+use serde::de::{self, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use fil_actors_runtime::DealWeight;
 
@@ -327,7 +330,7 @@ pub struct SectorPreCommitOnChainInfo {
 }
 
 /// Information stored on-chain for a proven sector.
-#[derive(Debug, Default, PartialEq, Eq, Clone, Serialize_tuple, Deserialize_tuple)]
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
 pub struct SectorOnChainInfo {
     pub sector_number: SectorNumber,
     /// The seal proof type implies the PoSt proofs
@@ -340,10 +343,8 @@ pub struct SectorOnChainInfo {
     /// Epoch during which the sector expires
     pub expiration: ChainEpoch,
     /// Integral of active deals over sector lifetime
-    #[serde(with = "bigint_ser")]
     pub deal_weight: DealWeight,
     /// Integral of active verified deals over sector lifetime
-    #[serde(with = "bigint_ser")]
     pub verified_deal_weight: DealWeight,
     /// Pledge collected to commit this sector
     pub initial_pledge: TokenAmount,
@@ -359,6 +360,87 @@ pub struct SectorOnChainInfo {
     pub sector_key_cid: Option<Cid>,
     // Flag for QA power mechanism introduced in fip 0045
     pub simple_qa_power: bool,
+    /// CommD, absent for committed-capacity sectors and sectors activated before it was recorded
+    pub unsealed_cid: Option<Cid>,
+}
+
+// Sectors without an unsealed CID are encoded as the 15-tuple they were before it was recorded.
+impl Serialize for SectorOnChainInfo {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let len = if self.unsealed_cid.is_some() { 16 } else { 15 };
+        let mut tuple = serializer.serialize_tuple(len)?;
+        tuple.serialize_element(&self.sector_number)?;
+        tuple.serialize_element(&self.seal_proof)?;
+        tuple.serialize_element(&self.sealed_cid)?;
+        tuple.serialize_element(&self.deal_ids)?;
+        tuple.serialize_element(&self.activation)?;
+        tuple.serialize_element(&self.expiration)?;
+        tuple.serialize_element(&bigint_ser::BigIntSer(&self.deal_weight))?;
+        tuple.serialize_element(&bigint_ser::BigIntSer(&self.verified_deal_weight))?;
+        tuple.serialize_element(&self.initial_pledge)?;
+        tuple.serialize_element(&self.expected_day_reward)?;
+        tuple.serialize_element(&self.expected_storage_pledge)?;
+        tuple.serialize_element(&self.power_base_epoch)?;
+        tuple.serialize_element(&self.replaced_day_reward)?;
+        tuple.serialize_element(&self.sector_key_cid)?;
+        tuple.serialize_element(&self.simple_qa_power)?;
+        if let Some(unsealed_cid) = &self.unsealed_cid {
+            tuple.serialize_element(unsealed_cid)?;
+        }
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for SectorOnChainInfo {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct SectorVisitor;
+
+        impl<'de> Visitor<'de> for SectorVisitor {
+            type Value = SectorOnChainInfo;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a tuple of 15 or 16 sector fields")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<SectorOnChainInfo, A::Error> {
+                fn required<'de, A: SeqAccess<'de>, T: Deserialize<'de>>(
+                    seq: &mut A,
+                    i: usize,
+                ) -> Result<T, A::Error> {
+                    seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &SectorVisitor))
+                }
+                Ok(SectorOnChainInfo {
+                    sector_number: required(&mut seq, 0)?,
+                    seal_proof: required(&mut seq, 1)?,
+                    sealed_cid: required(&mut seq, 2)?,
+                    deal_ids: required(&mut seq, 3)?,
+                    activation: required(&mut seq, 4)?,
+                    expiration: required(&mut seq, 5)?,
+                    deal_weight: required::<_, bigint_ser::BigIntDe>(&mut seq, 6)?.0,
+                    verified_deal_weight: required::<_, bigint_ser::BigIntDe>(&mut seq, 7)?.0,
+                    initial_pledge: required(&mut seq, 8)?,
+                    expected_day_reward: required(&mut seq, 9)?,
+                    expected_storage_pledge: required(&mut seq, 10)?,
+                    power_base_epoch: required(&mut seq, 11)?,
+                    replaced_day_reward: required(&mut seq, 12)?,
+                    sector_key_cid: required(&mut seq, 13)?,
+                    simple_qa_power: required(&mut seq, 14)?,
+                    unsealed_cid: seq.next_element()?.unwrap_or_default(),
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(SectorVisitor)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize_tuple, Deserialize_tuple)]
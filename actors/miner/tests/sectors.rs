@@ -3,6 +3,7 @@ use fil_actor_miner::Sectors;
 use fil_actors_runtime::test_utils::*;
 use fvm_ipld_bitfield::BitField;
 use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_ipld_encoding::{from_slice, to_vec};
 use fvm_shared::sector::RegisteredSealProof;
 
 mod util;
@@ -148,3 +149,22 @@ fn no_non_faulty_sectors() {
     let vec_sectors = sectors.load_for_proof(&bf_from_vec(vec![1]), &bf_from_vec(vec![1])).unwrap();
     assert_eq!(vec_sectors.len(), 0);
 }
+
+#[test]
+fn encodes_unsealed_cid_only_when_present() {
+    // A sector without an unsealed CID keeps the 15-tuple encoding it was stored with before.
+    let sector = make_sector(1);
+    let encoded = to_vec(&sector).unwrap();
+    // CBOR array header for 15 elements.
+    assert_eq!(encoded[0], 0x80 | 15);
+    assert_eq!(sector, from_slice(&encoded).unwrap());
+
+    let sector = SectorOnChainInfo {
+        unsealed_cid: Some(make_piece_cid(b"commD-1")),
+        deal_ids: vec![1, 2],
+        ..make_sector(1)
+    };
+    let encoded = to_vec(&sector).unwrap();
+    assert_eq!(encoded[0], 0x80 | 16);
+    assert_eq!(sector, from_slice(&encoded).unwrap());
+}
@@ -46,9 +46,9 @@ use fil_actor_miner::ext::verifreg::{
 use fil_actors_runtime::runtime::{DomainSeparationTag, Policy, Runtime, RuntimePolicy};
 use fil_actors_runtime::{test_utils::*, BatchReturnGen};
 use fil_actors_runtime::{
-    ActorDowncast, ActorError, Array, DealWeight, MessageAccumulator, BURNT_FUNDS_ACTOR_ADDR,
-    INIT_ACTOR_ADDR, REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR,
-    VERIFIED_REGISTRY_ACTOR_ADDR,
+    ActorDowncast, ActorError, Array, DealWeight, EventBuilder, MessageAccumulator,
+    BURNT_FUNDS_ACTOR_ADDR, INIT_ACTOR_ADDR, REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR,
+    STORAGE_POWER_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
 };
 use fvm_ipld_amt::Amt;
 use fvm_shared::bigint::Zero;
@@ -720,6 +720,16 @@ impl ActorHarness {
             );
         }
 
+        for sector in sectors.iter().sorted_by_key(|s| s.sector_number) {
+            expect_sector_data_event(
+                rt,
+                "sector-precommitted",
+                sector.sector_number,
+                sector.unsealed_cid.0,
+                &sector.deal_ids,
+            );
+        }
+
         let result = rt.call::<Actor>(method as u64, IpldBlock::serialize_cbor(&param).unwrap());
         result
     }
@@ -750,6 +760,8 @@ impl ActorHarness {
         rt.expect_validate_caller_addr(self.caller_addrs());
         self.expect_query_network_info(rt);
 
+        // Without deals, the sector has the unsealed CID of a committed-capacity sector.
+        let unsealed_cid = if params.deal_ids.is_empty() { None } else { conf.0.commd };
         if !params.deal_ids.is_empty() {
             let vdparams = VerifyDealsForActivationParams {
                 sectors: vec![SectorDeals {
@@ -800,6 +812,14 @@ impl ActorHarness {
             );
         }
 
+        expect_sector_data_event(
+            rt,
+            "sector-precommitted",
+            params.sector_number,
+            unsealed_cid,
+            &params.deal_ids,
+        );
+
         let result = rt.call::<Actor>(
             Method::PreCommitSector as u64,
             IpldBlock::serialize_cbor(&params.clone()).unwrap(),
@@ -1170,6 +1190,13 @@ impl ActorHarness {
                     );
 
                     expected_pledge += pledge;
+                    expect_sector_data_event(
+                        rt,
+                        "sector-activated",
+                        pc.info.sector_number,
+                        pc.info.unsealed_cid.0,
+                        &pc.info.deal_ids,
+                    );
                 }
             }
 
@@ -1311,6 +1338,20 @@ impl ActorHarness {
             );
         }
 
+        // Sectors reaching their expiration at this deadline are reported as terminated.
+        let expired =
+            state.clone().advance_deadline(&rt.policy, &rt.store, *rt.epoch.borrow()).unwrap();
+        for sector in state.load_sector_infos(&rt.store, &expired.on_time_expired_sectors).unwrap()
+        {
+            expect_sector_data_event(
+                rt,
+                "sector-terminated",
+                sector.sector_number,
+                sector.unsealed_cid,
+                &sector.deal_ids,
+            );
+        }
+
         let params = make_deferred_cron_event_params(
             self.epoch_reward_smooth.clone(),
             self.epoch_qa_power_smooth.clone(),
@@ -2084,6 +2125,16 @@ impl ActorHarness {
             ExitCode::OK,
         );
 
+        for sector in &sector_infos {
+            expect_sector_data_event(
+                rt,
+                "sector-terminated",
+                sector.sector_number,
+                sector.unsealed_cid,
+                &sector.deal_ids,
+            );
+        }
+
         // create declarations
         let state: State = rt.get_state();
         let deadlines = state.load_deadlines(rt.store()).unwrap();
@@ -2873,6 +2924,24 @@ fn make_piece_cid(input: &[u8]) -> Cid {
     Cid::new_v1(FIL_COMMITMENT_UNSEALED, h)
 }
 
+pub fn expect_sector_data_event(
+    rt: &MockRuntime,
+    typ: &str,
+    sector: SectorNumber,
+    unsealed_cid: Option<Cid>,
+    deals: &[DealID],
+) {
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ(typ)
+            .field_indexed("sector", &sector)
+            .field_indexed("unsealed-cid", &unsealed_cid)
+            .field("deals", deals)
+            .build()
+            .unwrap(),
+    );
+}
+
 pub fn make_deadline_cron_event_params(epoch: ChainEpoch) -> EnrollCronEventParams {
     let payload = CronEventPayload { event_type: CRON_EVENT_PROVING_DEADLINE };
     EnrollCronEventParams { event_epoch: epoch, payload: RawBytes::serialize(payload).unwrap() }
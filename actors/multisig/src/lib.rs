@@ -46,8 +46,11 @@ pub enum Method {
     SwapSigner = 7,
     ChangeNumApprovalsThreshold = 8,
     LockBalance = 9,
+    ExecuteBatch = 10,
     // Method numbers derived from FRC-0042 standards
     UniversalReceiverHook = frc42_dispatch::method_hash!("Receive"),
+    RemoveExpiredExported = frc42_dispatch::method_hash!("RemoveExpired"),
+    ProposeBatchExported = frc42_dispatch::method_hash!("ProposeBatch"),
 }
 
 /// Multisig Actor
@@ -126,6 +129,45 @@ impl Actor {
     /// Multisig actor propose function
     pub fn propose(rt: &impl Runtime, params: ProposeParams) -> Result<ProposeReturn, ActorError> {
         rt.validate_immediate_caller_accept_any()?;
+        Self::propose_transaction(rt, params)
+    }
+
+    /// Proposes a batch transaction, which executes a list of sends in order and atomically once
+    /// approved: if any send fails, none of them take effect.
+    pub fn propose_batch(
+        rt: &impl Runtime,
+        params: ProposeBatchParams,
+    ) -> Result<ProposeReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+
+        if params.sends.is_empty() {
+            return Err(actor_error!(illegal_argument, "batch must contain at least one send"));
+        }
+        if let Some(send) = params.sends.iter().find(|send| send.value.is_negative()) {
+            return Err(actor_error!(
+                illegal_argument,
+                "batch send value must be non-negative, was {}",
+                send.value
+            ));
+        }
+
+        let batch = ExecuteBatchParams { sends: params.sends };
+        Self::propose_transaction(
+            rt,
+            ProposeParams {
+                to: rt.message().receiver(),
+                value: TokenAmount::zero(),
+                method: Method::ExecuteBatch as MethodNum,
+                params: RawBytes::serialize(batch)?,
+                expiration: params.expiration,
+            },
+        )
+    }
+
+    fn propose_transaction(
+        rt: &impl Runtime,
+        params: ProposeParams,
+    ) -> Result<ProposeReturn, ActorError> {
         let proposer: Address = rt.message().caller();
 
         if params.value.is_negative() {
@@ -136,6 +178,17 @@ impl Actor {
             ));
         }
 
+        if let Some(expiration) = params.expiration {
+            if expiration <= rt.curr_epoch() {
+                return Err(actor_error!(
+                    illegal_argument,
+                    "expiration {} must be after the current epoch {}",
+                    expiration,
+                    rt.curr_epoch()
+                ));
+            }
+        }
+
         let (txn_id, txn) = rt.transaction(|st: &mut State, rt| {
             if !st.is_signer(&proposer) {
                 return Err(actor_error!(forbidden, "{} is not a signer", proposer));
@@ -153,6 +206,7 @@ impl Actor {
                 method: params.method,
                 params: params.params,
                 approved: Vec::new(),
+                expiration: params.expiration,
             };
 
             ptx.set(t_id.key(), txn.clone()).context_code(
@@ -188,6 +242,14 @@ impl Actor {
                 .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load pending transactions")?;
 
             let txn = get_transaction(rt, &ptx, params.id, params.proposal_hash)?;
+            if txn.is_expired(rt.curr_epoch()) {
+                return Err(actor_error!(
+                    forbidden,
+                    "transaction {} expired at epoch {}",
+                    params.id,
+                    txn.expiration.unwrap()
+                ));
+            }
 
             // Go implementation holds reference to state after transaction so state must be cloned
             // to match to handle possible exit code inconsistency
@@ -250,6 +312,43 @@ impl Actor {
         })
     }
 
+    /// Removes an expired transaction. May be called by anyone.
+    pub fn remove_expired(
+        rt: &impl Runtime,
+        params: RemoveExpiredParams,
+    ) -> Result<(), ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+
+        rt.transaction(|st: &mut State, rt| {
+            let mut ptx = make_map_with_root::<_, Transaction>(&st.pending_txs, rt.store())
+                .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load pending transactions")?;
+
+            let txn = ptx
+                .get(&params.id.key())
+                .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                    format!("failed to load transaction {:?} for removal", params.id)
+                })?
+                .ok_or_else(|| {
+                    actor_error!(not_found, "no such transaction {:?} to remove", params.id)
+                })?;
+
+            if !txn.is_expired(rt.curr_epoch()) {
+                return Err(actor_error!(forbidden, "transaction {} has not expired", params.id));
+            }
+
+            ptx.delete(&params.id.key()).with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("failed to delete transaction {:?}", params.id)
+            })?;
+
+            st.pending_txs = ptx.flush().context_code(
+                ExitCode::USR_ILLEGAL_STATE,
+                "failed to flush pending transactions",
+            )?;
+
+            Ok(())
+        })
+    }
+
     /// Multisig actor function to add signers to multisig
     pub fn add_signer(rt: &impl Runtime, params: AddSignerParams) -> Result<(), ActorError> {
         let receiver = rt.message().receiver();
@@ -400,6 +499,39 @@ impl Actor {
         Ok(())
     }
 
+    /// Executes the sends of an approved batch transaction in order. Any failed send aborts the
+    /// whole batch, reverting the sends before it.
+    ///
+    /// Permissions: May only be called by the multisig itself.
+    pub fn execute_batch(
+        rt: &impl Runtime,
+        params: ExecuteBatchParams,
+    ) -> Result<ExecuteBatchReturn, ActorError> {
+        let receiver = rt.message().receiver();
+        rt.validate_immediate_caller_is(std::iter::once(&receiver))?;
+
+        // The batch transaction itself sends no value, so check the whole batch against the
+        // locked balance here.
+        let total =
+            params.sends.iter().fold(TokenAmount::zero(), |total, send| total + &send.value);
+        let st: State = rt.state()?;
+        st.check_available(rt.current_balance(), &total, rt.curr_epoch())?;
+
+        let mut results = Vec::with_capacity(params.sends.len());
+        for (i, send) in params.sends.into_iter().enumerate() {
+            let ret = extract_send_result(rt.send_simple(
+                &send.to,
+                send.method,
+                send.params.into(),
+                send.value,
+            ))
+            .with_context(|| format!("batch send {} to {} failed", i, send.to))?;
+            results.push(ret.map_or_else(RawBytes::default, |r| RawBytes::new(r.data)));
+        }
+
+        Ok(ExecuteBatchReturn { results })
+    }
+
     fn approve_transaction(
         rt: &impl Runtime,
         tx_id: TxnID,
@@ -577,7 +709,10 @@ impl ActorCode for Actor {
       SwapSigner => swap_signer,
       ChangeNumApprovalsThreshold => change_num_approvals_threshold,
       LockBalance => lock_balance,
+      ExecuteBatch => execute_batch,
       UniversalReceiverHook => universal_receiver_hook,
+      RemoveExpiredExported => remove_expired,
+      ProposeBatchExported => propose_batch,
       _ => fallback [raw],
     }
 }
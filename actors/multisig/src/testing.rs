@@ -10,6 +10,8 @@ use crate::{State, Transaction, TxnID, SIGNERS_MAX};
 
 pub struct StateSummary {
     pub pending_tx_count: u64,
+    /// The number of pending transactions with an expiration epoch.
+    pub expiring_tx_count: u64,
     pub num_approvals_threshold: u64,
     pub signer_count: usize,
 }
@@ -53,6 +55,7 @@ pub fn check_state_invariants<BS: Blockstore>(
     // test pending transactions
     let mut max_tx_id = TxnID(-1);
     let mut pending_tx_count = 0u64;
+    let mut expiring_tx_count = 0u64;
 
     match Map::<_, Transaction>::load(&state.pending_txs, store) {
         Ok(transactions) => {
@@ -86,6 +89,15 @@ pub fn check_state_invariants<BS: Blockstore>(
                     format!("number of approvals ({}) meets the approvals threshold ({}), transaction should not be pending",
                     seen_approvals.len(), state.num_approvals_threshold));
 
+                if let Some(expiration) = transaction.expiration {
+                    // Proposals must expire after the epoch they're made in.
+                    acc.require(
+                        expiration > 0,
+                        format!("transaction {tx_id} has non-positive expiration {expiration}"),
+                    );
+                    expiring_tx_count += 1;
+                }
+
                 pending_tx_count += 1;
 
                 Ok(())
@@ -104,6 +116,7 @@ pub fn check_state_invariants<BS: Blockstore>(
     (
         StateSummary {
             pending_tx_count,
+            expiring_tx_count,
             num_approvals_threshold: state.num_approvals_threshold,
             signer_count: state.signers.len(),
         },
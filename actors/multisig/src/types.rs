@@ -1,7 +1,7 @@
 // Copyright 2019-2022 ChainSafe Systems
 // SPDX-License-Identifier: Apache-2.0, MIT
 
-use std::fmt::Display;
+use std::fmt::{self, Display};
 
 use fvm_ipld_encoding::tuple::*;
 use fvm_ipld_encoding::{strict_bytes, RawBytes};
@@ -13,7 +13,9 @@ use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
 use fvm_shared::MethodNum;
 use integer_encoding::VarInt;
-use serde::{Deserialize, Serialize};
+use serde::de::{self, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// SignersMax is the maximum number of signers allowed in a multisig. If more
 /// are required, please use a combining tree of multisigs.
@@ -37,7 +39,7 @@ impl Display for TxnID {
 }
 
 /// Transaction type used in multisig actor
-#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Transaction {
     pub to: Address,
     pub value: TokenAmount,
@@ -45,6 +47,70 @@ pub struct Transaction {
     pub params: RawBytes,
 
     pub approved: Vec<Address>,
+    /// The epoch from which the transaction can no longer be approved, if any.
+    /// Expired transactions may be removed by anyone.
+    // * Added in v13 -- changes the state schema. Transactions stored by earlier versions have
+    // no expiration.
+    pub expiration: Option<ChainEpoch>,
+}
+
+/// Serialize the transaction like a tuple, omitting an absent expiration so that transactions
+/// without one keep the encoding of earlier versions.
+impl Serialize for Transaction {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let len = if self.expiration.is_some() { 6 } else { 5 };
+        let mut tuple = serializer.serialize_tuple(len)?;
+        tuple.serialize_element(&self.to)?;
+        tuple.serialize_element(&self.value)?;
+        tuple.serialize_element(&self.method)?;
+        tuple.serialize_element(&self.params)?;
+        tuple.serialize_element(&self.approved)?;
+        if let Some(expiration) = &self.expiration {
+            tuple.serialize_element(expiration)?;
+        }
+        tuple.end()
+    }
+}
+
+/// Deserialize the transaction like a tuple, accepting transactions stored without an expiration.
+impl<'de> Deserialize<'de> for Transaction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TransactionVisitor;
+
+        impl<'de> Visitor<'de> for TransactionVisitor {
+            type Value = Transaction;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a multisig transaction")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Transaction, A::Error> {
+                Ok(Transaction {
+                    to: required(&mut seq, 0, &self)?,
+                    value: required(&mut seq, 1, &self)?,
+                    method: required(&mut seq, 2, &self)?,
+                    params: required(&mut seq, 3, &self)?,
+                    approved: required(&mut seq, 4, &self)?,
+                    expiration: seq.next_element()?.unwrap_or_default(),
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(TransactionVisitor)
+    }
+}
+
+impl Transaction {
+    /// Returns true if the transaction can no longer be approved at the given epoch.
+    pub fn is_expired(&self, epoch: ChainEpoch) -> bool {
+        self.expiration.map_or(false, |expiration| epoch >= expiration)
+    }
 }
 
 /// Data for a BLAKE2B-256 to be attached to methods referencing proposals via TXIDs.
@@ -73,12 +139,90 @@ pub struct ConstructorParams {
 }
 
 /// Propose method call parameters.
-#[derive(Serialize_tuple, Deserialize_tuple)]
+#[derive(Serialize_tuple)]
 pub struct ProposeParams {
     pub to: Address,
     pub value: TokenAmount,
     pub method: MethodNum,
     pub params: RawBytes,
+    /// The epoch from which the transaction can no longer be approved, if any.
+    // * Added in v13
+    pub expiration: Option<ChainEpoch>,
+}
+
+/// Deserialize the params like a tuple, accepting params without an expiration.
+impl<'de> Deserialize<'de> for ProposeParams {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ParamsVisitor;
+
+        impl<'de> Visitor<'de> for ParamsVisitor {
+            type Value = ProposeParams;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("propose params")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ProposeParams, A::Error> {
+                Ok(ProposeParams {
+                    to: required(&mut seq, 0, &self)?,
+                    value: required(&mut seq, 1, &self)?,
+                    method: required(&mut seq, 2, &self)?,
+                    params: required(&mut seq, 3, &self)?,
+                    expiration: seq.next_element()?.unwrap_or_default(),
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(ParamsVisitor)
+    }
+}
+
+/// Reads the next element of a tuple, failing if the tuple has ended.
+fn required<'de, A, T>(
+    seq: &mut A,
+    index: usize,
+    expected: &dyn de::Expected,
+) -> Result<T, A::Error>
+where
+    A: SeqAccess<'de>,
+    T: Deserialize<'de>,
+{
+    seq.next_element()?.ok_or_else(|| de::Error::invalid_length(index, expected))
+}
+
+/// A single send in a batch transaction.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct BatchSend {
+    pub to: Address,
+    pub value: TokenAmount,
+    pub method: MethodNum,
+    pub params: RawBytes,
+}
+
+/// ProposeBatch method call parameters.
+#[derive(Serialize_tuple, Deserialize_tuple)]
+pub struct ProposeBatchParams {
+    /// The sends to execute, in order, once the batch is approved.
+    pub sends: Vec<BatchSend>,
+    /// The epoch from which the transaction can no longer be approved, if any.
+    pub expiration: Option<ChainEpoch>,
+}
+
+/// ExecuteBatch method call parameters. A batch transaction is a transaction sending these to
+/// the multisig itself, so the proposal hash covers every send in the batch.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct ExecuteBatchParams {
+    pub sends: Vec<BatchSend>,
+}
+
+/// ExecuteBatch method call return.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct ExecuteBatchReturn {
+    /// The return value of each send, in order.
+    pub results: Vec<RawBytes>,
 }
 
 /// Propose method call return.
@@ -119,6 +263,12 @@ pub struct ApproveReturn {
     pub ret: RawBytes,
 }
 
+/// Remove expired transaction params.
+#[derive(Serialize_tuple, Deserialize_tuple)]
+pub struct RemoveExpiredParams {
+    pub id: TxnID,
+}
+
 /// Add signer params.
 #[derive(Serialize_tuple, Deserialize_tuple)]
 pub struct AddSignerParams {
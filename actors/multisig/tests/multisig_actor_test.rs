@@ -1,7 +1,7 @@
 use fil_actor_multisig::testing::check_state_invariants;
 use fil_actor_multisig::{
-    compute_proposal_hash, Actor as MultisigActor, ConstructorParams, Method, ProposeReturn, State,
-    Transaction, TxnID, TxnIDParams, SIGNERS_MAX,
+    compute_proposal_hash, Actor as MultisigActor, ConstructorParams, Method, ProposeParams,
+    ProposeReturn, State, Transaction, TxnID, TxnIDParams, SIGNERS_MAX,
 };
 use fil_actors_runtime::cbor::serialize;
 use fil_actors_runtime::runtime::Runtime;
@@ -574,6 +574,7 @@ fn test_simple_propose() {
         method: METHOD_SEND,
         params: RawBytes::default(),
         approved: vec![anne],
+        expiration: None,
     };
     let expect_txns = vec![(TxnID(0), txn0)];
     h.assert_transactions(&rt, expect_txns);
@@ -1144,6 +1145,7 @@ fn test_swap_signer_removes_approvals() {
                     method: METHOD_SEND,
                     params: RawBytes::default(),
                     approved: vec![bob],
+                    expiration: None,
                 },
             ),
             (
@@ -1154,6 +1156,7 @@ fn test_swap_signer_removes_approvals() {
                     method: METHOD_SEND,
                     params: RawBytes::default(),
                     approved: vec![bob],
+                    expiration: None,
                 },
             ),
         ],
@@ -1230,6 +1233,7 @@ fn test_remove_signer_removes_approvals() {
                     method: METHOD_SEND,
                     params: RawBytes::default(),
                     approved: vec![bob],
+                    expiration: None,
                 },
             ),
             (
@@ -1240,6 +1244,7 @@ fn test_remove_signer_removes_approvals() {
                     method: METHOD_SEND,
                     params: RawBytes::default(),
                     approved: vec![bob],
+                    expiration: None,
                 },
             ),
         ],
@@ -1304,6 +1309,7 @@ mod approval_tests {
             method: fake_method,
             params: fake_params.clone(),
             approved: vec![anne],
+            expiration: None,
         };
         h.assert_transactions(&rt, vec![(TxnID(0), expect_txn)]);
 
@@ -1390,6 +1396,7 @@ mod approval_tests {
                     method: fake_method,
                     params: fake_params.clone(),
                     approved: vec![anne],
+                    expiration: None,
                 },
             )],
         );
@@ -1440,6 +1447,7 @@ mod approval_tests {
                     method: fake_method,
                     params: fake_params,
                     approved: vec![anne],
+                    expiration: None,
                 },
             )],
         );
@@ -1477,6 +1485,7 @@ mod approval_tests {
                     method: fake_method,
                     params: fake_params,
                     approved: vec![anne],
+                    expiration: None,
                 },
             )],
         );
@@ -1511,6 +1520,7 @@ mod approval_tests {
                 method: fake_method,
                 params: fake_params,
                 approved: vec![bob], //mismatch
+                expiration: None,
             },
             &rt,
         )
@@ -1592,6 +1602,7 @@ mod approval_tests {
                     method: fake_method,
                     params: fake_params,
                     approved: vec![anne],
+                    expiration: None,
                 },
             )],
         );
@@ -1658,6 +1669,7 @@ mod approval_tests {
                     method: fake_method,
                     params: fake_params,
                     approved: vec![anne],
+                    expiration: None,
                 },
             )],
         );
@@ -1893,6 +1905,7 @@ mod cancel_tests {
                     method: fake_method,
                     params: RawBytes::default(),
                     approved: vec![anne],
+                    expiration: None,
                 },
             )],
         );
@@ -1934,6 +1947,7 @@ mod cancel_tests {
                     method: fake_method,
                     params: RawBytes::default(),
                     approved: vec![anne],
+                    expiration: None,
                 },
             )],
         );
@@ -1976,6 +1990,7 @@ mod cancel_tests {
                     method: fake_method,
                     params: RawBytes::default(),
                     approved: vec![anne],
+                    expiration: None,
                 },
             )],
         );
@@ -2028,6 +2043,7 @@ mod cancel_tests {
             method: fake_method,
             params: RawBytes::default(),
             approved: vec![bob], // anne's approval is gone
+            expiration: None,
         };
         let new_proposal_hash = compute_proposal_hash(&new_tx, &rt).unwrap();
         h.assert_transactions(&rt, vec![(TxnID(0), new_tx)]);
@@ -2039,6 +2055,418 @@ mod cancel_tests {
     }
 }
 
+// Expiry
+mod expiry_tests {
+    use super::*;
+
+    fn expiring_txn(to: Address, approved: Vec<Address>, expiration: ChainEpoch) -> Transaction {
+        Transaction {
+            to,
+            value: TokenAmount::zero(),
+            method: METHOD_SEND,
+            params: RawBytes::default(),
+            approved,
+            expiration: Some(expiration),
+        }
+    }
+
+    #[test]
+    fn test_approve_before_expiration() {
+        let msig = Address::new_id(100);
+        let anne = Address::new_id(101);
+        let bob = Address::new_id(102);
+        let chuck = Address::new_id(103);
+        let rt = construct_runtime(msig);
+        let h = util::ActorHarness::new();
+        h.construct_and_verify(&rt, 2, 0, 0, vec![anne, bob]);
+
+        rt.set_epoch(10);
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+        h.propose_with_expiration(
+            &rt,
+            chuck,
+            TokenAmount::zero(),
+            METHOD_SEND,
+            RawBytes::default(),
+            Some(20),
+        )
+        .unwrap();
+        h.assert_transactions(&rt, vec![(TxnID(0), expiring_txn(chuck, vec![anne], 20))]);
+        check_state(&rt);
+
+        // The last epoch before expiration may still approve.
+        rt.set_epoch(19);
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, bob);
+        rt.expect_send_simple(chuck, METHOD_SEND, None, TokenAmount::zero(), None, ExitCode::OK);
+        let proposal_hash =
+            compute_proposal_hash(&expiring_txn(chuck, vec![anne], 20), &rt).unwrap();
+        h.approve_ok(&rt, TxnID(0), proposal_hash);
+        h.assert_transactions(&rt, vec![]);
+        check_state(&rt);
+    }
+
+    #[test]
+    fn test_approve_expired_fails() {
+        let msig = Address::new_id(100);
+        let anne = Address::new_id(101);
+        let bob = Address::new_id(102);
+        let chuck = Address::new_id(103);
+        let rt = construct_runtime(msig);
+        let h = util::ActorHarness::new();
+        h.construct_and_verify(&rt, 2, 0, 0, vec![anne, bob]);
+
+        rt.set_epoch(10);
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+        h.propose_with_expiration(
+            &rt,
+            chuck,
+            TokenAmount::zero(),
+            METHOD_SEND,
+            RawBytes::default(),
+            Some(20),
+        )
+        .unwrap();
+
+        rt.set_epoch(20);
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, bob);
+        let proposal_hash =
+            compute_proposal_hash(&expiring_txn(chuck, vec![anne], 20), &rt).unwrap();
+        expect_abort(ExitCode::USR_FORBIDDEN, h.approve(&rt, TxnID(0), proposal_hash));
+        h.assert_transactions(&rt, vec![(TxnID(0), expiring_txn(chuck, vec![anne], 20))]);
+        check_state(&rt);
+    }
+
+    #[test]
+    fn test_propose_past_expiration_fails() {
+        let msig = Address::new_id(100);
+        let anne = Address::new_id(101);
+        let bob = Address::new_id(102);
+        let rt = construct_runtime(msig);
+        let h = util::ActorHarness::new();
+        h.construct_and_verify(&rt, 2, 0, 0, vec![anne, bob]);
+
+        rt.set_epoch(10);
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+        expect_abort(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            h.propose_with_expiration(
+                &rt,
+                bob,
+                TokenAmount::zero(),
+                METHOD_SEND,
+                RawBytes::default(),
+                Some(10),
+            ),
+        );
+        h.assert_transactions(&rt, vec![]);
+        check_state(&rt);
+    }
+
+    #[test]
+    fn test_remove_expired() {
+        let msig = Address::new_id(100);
+        let anne = Address::new_id(101);
+        let bob = Address::new_id(102);
+        let chuck = Address::new_id(103);
+        let rt = construct_runtime(msig);
+        let h = util::ActorHarness::new();
+        h.construct_and_verify(&rt, 2, 0, 0, vec![anne, bob]);
+
+        rt.set_epoch(10);
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+        h.propose_with_expiration(
+            &rt,
+            chuck,
+            TokenAmount::zero(),
+            METHOD_SEND,
+            RawBytes::default(),
+            Some(20),
+        )
+        .unwrap();
+        h.propose(&rt, chuck, TokenAmount::zero(), METHOD_SEND, RawBytes::default()).unwrap();
+
+        // Anyone can remove an expired transaction, but only once it has expired.
+        rt.set_caller(*EVM_ACTOR_CODE_ID, chuck);
+        expect_abort(ExitCode::USR_FORBIDDEN, h.remove_expired(&rt, TxnID(0)));
+
+        rt.set_epoch(20);
+        h.remove_expired(&rt, TxnID(0)).unwrap();
+
+        // Transactions without an expiration never expire.
+        expect_abort(ExitCode::USR_FORBIDDEN, h.remove_expired(&rt, TxnID(1)));
+        expect_abort(ExitCode::USR_NOT_FOUND, h.remove_expired(&rt, TxnID(0)));
+
+        let (summary, acc) = check_state_invariants(&rt.get_state(), rt.store());
+        acc.assert_empty();
+        assert_eq!(1, summary.pending_tx_count);
+        assert_eq!(0, summary.expiring_tx_count);
+    }
+    #[test]
+    fn test_decode_without_expiration() {
+        #[derive(Serialize_tuple)]
+        struct LegacyTransaction {
+            to: Address,
+            value: TokenAmount,
+            method: MethodNum,
+            params: RawBytes,
+            approved: Vec<Address>,
+        }
+        #[derive(Serialize_tuple)]
+        struct LegacyProposeParams {
+            to: Address,
+            value: TokenAmount,
+            method: MethodNum,
+            params: RawBytes,
+        }
+
+        // Transactions stored before expirations were added.
+        let to = Address::new_id(101);
+        let legacy = LegacyTransaction {
+            to,
+            value: TokenAmount::from_atto(5),
+            method: METHOD_SEND,
+            params: RawBytes::default(),
+            approved: vec![Address::new_id(102)],
+        };
+        let txn: Transaction =
+            fvm_ipld_encoding::from_slice(&serialize(&legacy, "").unwrap()).unwrap();
+        assert_eq!(
+            Transaction {
+                to,
+                value: TokenAmount::from_atto(5),
+                method: METHOD_SEND,
+                params: RawBytes::default(),
+                approved: vec![Address::new_id(102)],
+                expiration: None,
+            },
+            txn
+        );
+        // And they're written back unchanged.
+        assert_eq!(serialize(&legacy, "").unwrap(), serialize(&txn, "").unwrap());
+
+        let txn = expiring_txn(to, vec![Address::new_id(102)], 10);
+        let decoded: Transaction =
+            fvm_ipld_encoding::from_slice(&serialize(&txn, "").unwrap()).unwrap();
+        assert_eq!(txn, decoded);
+
+        // Params from callers that don't know about expirations.
+        let legacy = LegacyProposeParams {
+            to,
+            value: TokenAmount::zero(),
+            method: 7,
+            params: RawBytes::default(),
+        };
+        let params: ProposeParams =
+            fvm_ipld_encoding::from_slice(&serialize(&legacy, "").unwrap()).unwrap();
+        assert_eq!((to, 7, None), (params.to, params.method, params.expiration));
+
+        // The other fields are still required.
+        let short = (to, TokenAmount::zero(), 7u64);
+        assert!(fvm_ipld_encoding::from_slice::<ProposeParams>(&serialize(&short, "").unwrap())
+            .is_err());
+        assert!(
+            fvm_ipld_encoding::from_slice::<Transaction>(&serialize(&short, "").unwrap()).is_err()
+        );
+    }
+}
+
+// Batch transactions
+mod batch_tests {
+    use super::*;
+    use fil_actor_multisig::{ApproveReturn, BatchSend, ExecuteBatchParams, ExecuteBatchReturn};
+
+    fn batch(chuck: Address, darlene: Address) -> Vec<BatchSend> {
+        vec![
+            BatchSend {
+                to: chuck,
+                value: TokenAmount::from_atto(10),
+                method: METHOD_SEND,
+                params: RawBytes::default(),
+            },
+            BatchSend {
+                to: darlene,
+                value: TokenAmount::from_atto(20),
+                method: 42,
+                params: RawBytes::from(vec![1, 2, 3]),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_propose_and_approve_batch() {
+        let msig = Address::new_id(100);
+        let anne = Address::new_id(101);
+        let bob = Address::new_id(102);
+        let chuck = Address::new_id(103);
+        let darlene = Address::new_id(104);
+        let rt = construct_runtime(msig);
+        let h = util::ActorHarness::new();
+        h.construct_and_verify(&rt, 2, 0, 0, vec![anne, bob]);
+
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+        h.propose_batch(&rt, batch(chuck, darlene)).unwrap();
+
+        // The batch is a transaction executing the sends through the multisig itself.
+        let batch_params =
+            RawBytes::serialize(ExecuteBatchParams { sends: batch(chuck, darlene) }).unwrap();
+        let expect_txn = Transaction {
+            to: msig,
+            value: TokenAmount::zero(),
+            method: Method::ExecuteBatch as MethodNum,
+            params: batch_params.clone(),
+            approved: vec![anne],
+            expiration: None,
+        };
+        h.assert_transactions(&rt, vec![(TxnID(0), expect_txn.clone())]);
+        check_state(&rt);
+
+        // The proposal hash covers every send in the batch.
+        let mut other_sends = batch(chuck, darlene);
+        other_sends[1].value = TokenAmount::from_atto(21);
+        let other_txn = Transaction {
+            params: RawBytes::serialize(ExecuteBatchParams { sends: other_sends }).unwrap(),
+            ..expect_txn.clone()
+        };
+        let other_hash = compute_proposal_hash(&other_txn, &rt).unwrap();
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, bob);
+        expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, h.approve(&rt, TxnID(0), other_hash));
+
+        // Approving executes the batch, returning each send's result.
+        let batch_ret =
+            ExecuteBatchReturn { results: vec![RawBytes::default(), RawBytes::from(vec![4, 5])] };
+        rt.expect_send_simple(
+            msig,
+            Method::ExecuteBatch as MethodNum,
+            to_ipld_block(batch_params),
+            TokenAmount::zero(),
+            IpldBlock::serialize_cbor(&batch_ret).unwrap(),
+            ExitCode::OK,
+        );
+        let proposal_hash = compute_proposal_hash(&expect_txn, &rt).unwrap();
+        let ret: ApproveReturn =
+            h.approve(&rt, TxnID(0), proposal_hash).unwrap().unwrap().deserialize().unwrap();
+        assert!(ret.applied);
+        assert_eq!(ExitCode::OK, ret.code);
+        assert_eq!(batch_ret, fvm_ipld_encoding::from_slice(&ret.ret).unwrap());
+        h.assert_transactions(&rt, vec![]);
+        check_state(&rt);
+    }
+
+    #[test]
+    fn test_propose_empty_batch_fails() {
+        let msig = Address::new_id(100);
+        let anne = Address::new_id(101);
+        let rt = construct_runtime(msig);
+        let h = util::ActorHarness::new();
+        h.construct_and_verify(&rt, 1, 0, 0, vec![anne]);
+
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+        expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, h.propose_batch(&rt, vec![]));
+        check_state(&rt);
+    }
+
+    #[test]
+    fn test_execute_batch() {
+        let msig = Address::new_id(100);
+        let anne = Address::new_id(101);
+        let chuck = Address::new_id(103);
+        let darlene = Address::new_id(104);
+        let rt = construct_runtime(msig);
+        let h = util::ActorHarness::new();
+        h.construct_and_verify(&rt, 1, 0, 0, vec![anne]);
+
+        rt.set_balance(TokenAmount::from_atto(30));
+        rt.set_caller(*MULTISIG_ACTOR_CODE_ID, msig);
+        rt.expect_send_simple(
+            chuck,
+            METHOD_SEND,
+            None,
+            TokenAmount::from_atto(10),
+            None,
+            ExitCode::OK,
+        );
+        rt.expect_send_simple(
+            darlene,
+            42,
+            to_ipld_block(RawBytes::from(vec![1, 2, 3])),
+            TokenAmount::from_atto(20),
+            to_ipld_block(RawBytes::from(vec![4, 5])),
+            ExitCode::OK,
+        );
+        let ret: ExecuteBatchReturn =
+            h.execute_batch(&rt, batch(chuck, darlene)).unwrap().unwrap().deserialize().unwrap();
+        assert_eq!(vec![RawBytes::default(), RawBytes::from(vec![4, 5])], ret.results);
+        check_state(&rt);
+    }
+
+    #[test]
+    fn test_execute_batch_aborts_on_failed_send() {
+        let msig = Address::new_id(100);
+        let anne = Address::new_id(101);
+        let chuck = Address::new_id(103);
+        let darlene = Address::new_id(104);
+        let rt = construct_runtime(msig);
+        let h = util::ActorHarness::new();
+        h.construct_and_verify(&rt, 1, 0, 0, vec![anne]);
+
+        rt.set_balance(TokenAmount::from_atto(30));
+        rt.set_caller(*MULTISIG_ACTOR_CODE_ID, msig);
+        rt.expect_send_simple(
+            chuck,
+            METHOD_SEND,
+            None,
+            TokenAmount::from_atto(10),
+            None,
+            ExitCode::OK,
+        );
+        rt.expect_send_simple(
+            darlene,
+            42,
+            to_ipld_block(RawBytes::from(vec![1, 2, 3])),
+            TokenAmount::from_atto(20),
+            None,
+            ExitCode::USR_ILLEGAL_STATE,
+        );
+        expect_abort_contains_message(
+            ExitCode::USR_ILLEGAL_STATE,
+            "batch send 1",
+            h.execute_batch(&rt, batch(chuck, darlene)),
+        );
+        check_state(&rt);
+    }
+
+    #[test]
+    fn test_execute_batch_respects_locked_balance() {
+        let msig = Address::new_id(100);
+        let anne = Address::new_id(101);
+        let chuck = Address::new_id(103);
+        let darlene = Address::new_id(104);
+        let rt = construct_runtime(msig);
+        let h = util::ActorHarness::new();
+        h.construct_and_verify(&rt, 1, 0, 0, vec![anne]);
+
+        // Each send is affordable, but not the whole batch.
+        rt.set_balance(TokenAmount::from_atto(25));
+        rt.set_caller(*MULTISIG_ACTOR_CODE_ID, msig);
+        expect_abort(ExitCode::USR_INSUFFICIENT_FUNDS, h.execute_batch(&rt, batch(chuck, darlene)));
+        check_state(&rt);
+    }
+
+    #[test]
+    fn test_execute_batch_only_by_self() {
+        let msig = Address::new_id(100);
+        let anne = Address::new_id(101);
+        let chuck = Address::new_id(103);
+        let darlene = Address::new_id(104);
+        let rt = construct_runtime(msig);
+        let h = util::ActorHarness::new();
+        h.construct_and_verify(&rt, 1, 0, 0, vec![anne]);
+
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+        expect_abort(ExitCode::USR_FORBIDDEN, h.execute_batch(&rt, batch(chuck, darlene)));
+    }
+}
+
 // ChangeNumApprovalsThreshold
 mod change_threshold_tests {
     use super::*;
@@ -3,7 +3,10 @@ use fil_actor_multisig::{
     ProposeParams, ProposeReturn, RemoveSignerParams, State, SwapSignerParams, Transaction, TxnID,
     TxnIDParams,
 };
-use fil_actor_multisig::{ChangeNumApprovalsThresholdParams, LockBalanceParams};
+use fil_actor_multisig::{
+    BatchSend, ChangeNumApprovalsThresholdParams, ExecuteBatchParams, LockBalanceParams,
+    ProposeBatchParams, RemoveExpiredParams,
+};
 use fil_actors_runtime::test_utils::*;
 use fil_actors_runtime::INIT_ACTOR_ADDR;
 use fil_actors_runtime::{make_map_with_root, ActorError};
@@ -103,7 +106,14 @@ impl ActorHarness {
         let ret = self.propose(rt, to, value.clone(), method, params.clone());
         ret.unwrap().unwrap().deserialize::<ProposeReturn>().unwrap();
         // compute proposal hash
-        let txn = Transaction { to, value, method, params, approved: vec![*rt.caller.borrow()] };
+        let txn = Transaction {
+            to,
+            value,
+            method,
+            params,
+            approved: vec![*rt.caller.borrow()],
+            expiration: None,
+        };
         compute_proposal_hash(&txn, rt).unwrap()
     }
 
@@ -123,9 +133,21 @@ impl ActorHarness {
         value: TokenAmount,
         method: MethodNum,
         params: RawBytes,
+    ) -> Result<Option<IpldBlock>, ActorError> {
+        self.propose_with_expiration(rt, to, value, method, params, None)
+    }
+
+    pub fn propose_with_expiration(
+        &self,
+        rt: &MockRuntime,
+        to: Address,
+        value: TokenAmount,
+        method: MethodNum,
+        params: RawBytes,
+        expiration: Option<ChainEpoch>,
     ) -> Result<Option<IpldBlock>, ActorError> {
         rt.expect_validate_caller_any();
-        let propose_params = ProposeParams { to, value, method, params };
+        let propose_params = ProposeParams { to, value, method, params, expiration };
         let ret = rt.call::<Actor>(
             Method::Propose as u64,
             IpldBlock::serialize_cbor(&propose_params).unwrap(),
@@ -168,6 +190,51 @@ impl ActorHarness {
         ret
     }
 
+    pub fn propose_batch(
+        &self,
+        rt: &MockRuntime,
+        sends: Vec<BatchSend>,
+    ) -> Result<Option<IpldBlock>, ActorError> {
+        rt.expect_validate_caller_any();
+        let propose_params = ProposeBatchParams { sends, expiration: None };
+        let ret = rt.call::<Actor>(
+            Method::ProposeBatchExported as u64,
+            IpldBlock::serialize_cbor(&propose_params).unwrap(),
+        );
+        rt.verify();
+        ret
+    }
+
+    pub fn execute_batch(
+        &self,
+        rt: &MockRuntime,
+        sends: Vec<BatchSend>,
+    ) -> Result<Option<IpldBlock>, ActorError> {
+        rt.expect_validate_caller_addr(vec![rt.receiver]);
+        let execute_params = ExecuteBatchParams { sends };
+        let ret = rt.call::<Actor>(
+            Method::ExecuteBatch as u64,
+            IpldBlock::serialize_cbor(&execute_params).unwrap(),
+        );
+        rt.verify();
+        ret
+    }
+
+    pub fn remove_expired(
+        &self,
+        rt: &MockRuntime,
+        txn_id: TxnID,
+    ) -> Result<Option<IpldBlock>, ActorError> {
+        rt.expect_validate_caller_any();
+        let remove_params = RemoveExpiredParams { id: txn_id };
+        let ret = rt.call::<Actor>(
+            Method::RemoveExpiredExported as u64,
+            IpldBlock::serialize_cbor(&remove_params).unwrap(),
+        );
+        rt.verify();
+        ret
+    }
+
     pub fn lock_balance(
         &self,
         rt: &MockRuntime,
@@ -42,6 +42,7 @@ pub use self::types::*;
 #[cfg(feature = "fil-actor")]
 fil_actors_runtime::wasm_trampoline!(Actor);
 
+mod emit;
 pub mod expiration;
 pub mod ext;
 pub mod state;
@@ -127,7 +128,9 @@ impl Actor {
         rt.transaction(|st: &mut State, rt| {
             st.put_verifier(rt.store(), &verifier, &params.allowance)
                 .context("failed to add verifier")
-        })
+        })?;
+
+        emit::verifier_balance(rt, verifier.id().unwrap(), &params.allowance)
     }
 
     pub fn remove_verifier(
@@ -140,7 +143,9 @@ impl Actor {
         rt.transaction(|st: &mut State, rt| {
             rt.validate_immediate_caller_is(std::iter::once(&st.root_key))?;
             st.remove_verifier(rt.store(), &verifier).context("failed to remove verifier")
-        })
+        })?;
+
+        emit::verifier_balance(rt, verifier.id().unwrap(), &DataCap::zero())
     }
 
     pub fn add_verified_client(
@@ -162,7 +167,7 @@ impl Actor {
         let client = resolve_to_actor_id(rt, &params.address, true)?;
         let client = Address::new_id(client);
 
-        rt.transaction(|st: &mut State, rt| {
+        let (verifier, new_verifier_cap) = rt.transaction(|st: &mut State, rt| {
             if client == st.root_key {
                 return Err(actor_error!(illegal_argument, "root cannot be added as client"));
             }
@@ -195,7 +200,8 @@ impl Actor {
             // Reduce verifier's cap.
             let new_verifier_cap = verifier_cap - &params.allowance;
             st.put_verifier(rt.store(), &verifier, &new_verifier_cap)
-                .context("failed to update verifier allowance")
+                .context("failed to update verifier allowance")?;
+            Ok((verifier, new_verifier_cap))
         })?;
 
         // Credit client token allowance.
@@ -204,7 +210,8 @@ impl Actor {
             "failed to mint {} data cap to client {}",
             &params.allowance, client
         ))?;
-        Ok(())
+
+        emit::verifier_balance(rt, verifier.id().unwrap(), &new_verifier_cap)
     }
 
     /// Removes DataCap allocated to a verified client.
@@ -313,6 +320,7 @@ impl Actor {
         let mut batch_ret = BatchReturn::empty();
         let mut considered = Vec::<ClaimID>::new();
         let mut recovered_datacap = DataCap::zero();
+        let mut removed = Vec::<(AllocationID, Allocation)>::new();
         let recovered_datacap = rt
             .transaction(|st: &mut State, rt| {
                 let mut allocs = st.load_allocs(rt.store())?;
@@ -340,7 +348,9 @@ impl Actor {
                         format!("failed to remove allocation {}", id),
                     )?;
                     // Unwrapping here as both paths to here should ensure the allocation exists.
-                    recovered_datacap += existing.unwrap().size.0;
+                    let existing = existing.unwrap();
+                    recovered_datacap += existing.size.0;
+                    removed.push((id, existing));
                 }
 
                 st.save_allocs(&mut allocs)?;
@@ -356,6 +366,10 @@ impl Actor {
             )
         })?;
 
+        for (id, alloc) in removed {
+            emit::allocation_removed(rt, id, alloc.client, alloc.provider)?;
+        }
+
         Ok(RemoveExpiredAllocationsReturn {
             considered,
             results: batch_ret,
@@ -382,6 +396,7 @@ impl Actor {
         let provider = rt.message().caller().id().unwrap();
         let mut total_datacap_claimed = DataCap::zero();
         let mut sector_claims = Vec::new();
+        let mut claimed = Vec::<(ClaimID, ActorID)>::new();
 
         rt.transaction(|st: &mut State, rt| {
             let mut claims = st.load_claims(rt.store())?;
@@ -449,6 +464,7 @@ impl Actor {
                 total_datacap_claimed += DataCap::from(claim_alloc.size.0);
                 sector_claims
                     .push(SectorAllocationClaimResult { claimed_space: claim_alloc.size.0.into() });
+                claimed.push((claim_alloc.allocation_id, claim_alloc.client));
             }
             st.save_allocs(&mut allocs)?;
             st.save_claims(&mut claims)?;
@@ -466,6 +482,10 @@ impl Actor {
         // Burn the datacap tokens from verified registry's own balance.
         burn(rt, &total_datacap_claimed)?;
 
+        for (id, client) in claimed {
+            emit::claim(rt, id, client, provider)?;
+        }
+
         Ok(ClaimAllocationsReturn { claim_results: sector_claims })
     }
 
@@ -512,6 +532,7 @@ impl Actor {
         let caller_id = rt.message().caller().id().unwrap();
         let term_limit = rt.policy().maximum_verified_allocation_term;
         let mut batch_gen = BatchReturnGen::new(params.terms.len());
+        let mut updated = Vec::<(ClaimID, ActorID, ActorID)>::new();
         rt.transaction(|st: &mut State, rt| {
             let mut st_claims = st.load_claims(rt.store())?;
             for term in params.terms {
@@ -552,6 +573,7 @@ impl Actor {
                         "HAMT put failure storing new claims",
                     )?;
                     batch_gen.add_success();
+                    updated.push((term.claim_id, caller_id, term.provider));
                 } else {
                     batch_gen.add_fail(ExitCode::USR_NOT_FOUND);
                     info!("no claim {} for provider {}", term.claim_id, term.provider);
@@ -561,6 +583,10 @@ impl Actor {
             Ok(())
         })
         .context("state transaction failed")?;
+
+        for (id, client, provider) in updated {
+            emit::claim_updated(rt, id, client, provider)?;
+        }
         Ok(batch_gen.gen())
     }
 
@@ -575,6 +601,7 @@ impl Actor {
         let curr_epoch = rt.curr_epoch();
         let mut batch_ret = BatchReturn::empty();
         let mut considered = Vec::<ClaimID>::new();
+        let mut removed = Vec::<(ClaimID, Claim)>::new();
         rt.transaction(|st: &mut State, rt| {
             let mut claims = st.load_claims(rt.store())?;
             let to_remove: Vec<ClaimID>;
@@ -595,10 +622,12 @@ impl Actor {
             }
 
             for id in to_remove {
-                claims.remove(params.provider, id).context_code(
+                let existing = claims.remove(params.provider, id).context_code(
                     ExitCode::USR_ILLEGAL_STATE,
                     format!("failed to remove claim {}", id),
                 )?;
+                // Unwrapping here as both paths to here should ensure the claim exists.
+                removed.push((id, existing.unwrap()));
             }
 
             st.save_claims(&mut claims)?;
@@ -606,6 +635,10 @@ impl Actor {
         })
         .context("state transaction failed")?;
 
+        for (id, claim) in removed {
+            emit::claim_removed(rt, id, claim.client, claim.provider)?;
+        }
+
         Ok(RemoveExpiredClaimsReturn { considered, results: batch_ret })
     }
 
@@ -694,12 +727,23 @@ impl Actor {
         let extension_results = BatchReturn::ok(updated_claims.len() as u32);
 
         // Save new allocations and updated claims.
+        let updated = updated_claims
+            .iter()
+            .map(|(id, claim)| (*id, claim.client, claim.provider))
+            .collect::<Vec<_>>();
         let ids = rt.transaction(|st: &mut State, rt| {
             let ids = st.insert_allocations(rt.store(), client, new_allocs)?;
             st.put_claims(rt.store(), updated_claims)?;
             Ok(ids)
         })?;
 
+        for (id, req) in ids.iter().zip(reqs.allocations.iter()) {
+            emit::allocation(rt, *id, client, req.provider)?;
+        }
+        for (id, client, provider) in updated {
+            emit::claim_updated(rt, id, client, provider)?;
+        }
+
         Ok(AllocationsResponse { allocation_results, extension_results, new_allocations: ids })
     }
 }
@@ -15,7 +15,7 @@ use fil_actors_runtime::runtime::policy_constants::{
 use fil_actors_runtime::runtime::Runtime;
 use fil_actors_runtime::test_utils::*;
 use fil_actors_runtime::{
-    make_empty_map, ActorError, AsActorError, BatchReturn, DATACAP_TOKEN_ACTOR_ADDR,
+    make_empty_map, ActorError, AsActorError, BatchReturn, EventBuilder, DATACAP_TOKEN_ACTOR_ADDR,
     STORAGE_MARKET_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
 };
 use frc46_token::receiver::{FRC46TokenReceived, FRC46_TOKEN_TYPE};
@@ -133,6 +133,10 @@ impl Harness {
             ExitCode::OK,
         );
 
+        if let Ok(verifier_id) = verifier_resolved.id() {
+            expect_verifier_balance_event(rt, verifier_id, allowance);
+        }
+
         let params = AddVerifierParams { address: *verifier, allowance: allowance.clone() };
         let ret = rt.call::<VerifregActor>(
             Method::AddVerifier as MethodNum,
@@ -148,6 +152,9 @@ impl Harness {
     pub fn remove_verifier(&self, rt: &MockRuntime, verifier: &Address) -> Result<(), ActorError> {
         rt.expect_validate_caller_addr(vec![self.root]);
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, self.root);
+        if let Ok(verifier_id) = rt.get_id_address(verifier).unwrap_or(*verifier).id() {
+            expect_verifier_balance_event(rt, verifier_id, &DataCap::zero());
+        }
         let ret = rt.call::<VerifregActor>(
             Method::RemoveVerifier as MethodNum,
             IpldBlock::serialize_cbor(verifier).unwrap(),
@@ -455,6 +462,38 @@ impl Harness {
     }
 }
 
+pub fn expect_verifier_balance_event(rt: &MockRuntime, verifier: ActorID, balance: &DataCap) {
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ("verifier-balance")
+            .field_indexed("verifier", &verifier)
+            .field("balance", &BigIntSer(balance))
+            .build()
+            .unwrap(),
+    );
+}
+
+// Expects an allocation or claim event, which share a schema.
+// Harness methods don't predict these events, so tests list the events they expect before
+// invoking the actor.
+pub fn expect_claim_event(
+    rt: &MockRuntime,
+    typ: &str,
+    id: u64,
+    client: ActorID,
+    provider: ActorID,
+) {
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ(typ)
+            .field_indexed("id", &id)
+            .field_indexed("client", &client)
+            .field_indexed("provider", &provider)
+            .build()
+            .unwrap(),
+    );
+}
+
 pub fn make_alloc(data_id: &str, client: ActorID, provider: ActorID, size: u64) -> Allocation {
     Allocation {
         client,
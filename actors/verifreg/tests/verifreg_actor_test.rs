@@ -148,6 +148,7 @@ mod verifiers {
             ExitCode::USR_ILLEGAL_ARGUMENT,
             h.add_verifier_with_existing_cap(&rt, &VERIFIER, &allowance, &DataCap::from(1)),
         );
+        rt.reset();
         h.check_state(&rt);
     }
 
@@ -219,6 +220,7 @@ mod verifiers {
     fn remove_requires_verifier_exists() {
         let (h, rt) = new_harness();
         expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, h.remove_verifier(&rt, &VERIFIER));
+        rt.reset();
         h.check_state(&rt);
     }
 
@@ -274,10 +276,14 @@ mod clients {
         h.add_verifier(&rt, &VERIFIER, &allowance_verifier).unwrap();
         h.add_verifier(&rt, &VERIFIER2, &allowance_verifier).unwrap();
 
+        expect_verifier_balance_event(&rt, VERIFIER.id().unwrap(), &allowance_client);
         h.add_client(&rt, &VERIFIER, &CLIENT, &allowance_client).unwrap();
+        expect_verifier_balance_event(&rt, VERIFIER.id().unwrap(), &DataCap::zero());
         h.add_client(&rt, &VERIFIER, &CLIENT2, &allowance_client).unwrap();
 
+        expect_verifier_balance_event(&rt, VERIFIER2.id().unwrap(), &allowance_client);
         h.add_client(&rt, &VERIFIER2, &CLIENT3, &allowance_client).unwrap();
+        expect_verifier_balance_event(&rt, VERIFIER2.id().unwrap(), &DataCap::zero());
         h.add_client(&rt, &VERIFIER2, &CLIENT4, &allowance_client).unwrap();
 
         // No more allowance left
@@ -293,6 +299,7 @@ mod clients {
         // Verifier only has allowance for one client.
         h.add_verifier(&rt, &VERIFIER, &allowance).unwrap();
 
+        expect_verifier_balance_event(&rt, VERIFIER.id().unwrap(), &DataCap::zero());
         h.add_client(&rt, &VERIFIER, &CLIENT, &allowance).unwrap();
         expect_abort(
             ExitCode::USR_ILLEGAL_ARGUMENT,
@@ -313,11 +320,13 @@ mod clients {
         rt.id_addresses.borrow_mut().insert(client_pubkey, *CLIENT);
 
         h.add_verifier(&rt, &VERIFIER, &allowance_verifier).unwrap();
+        expect_verifier_balance_event(&rt, VERIFIER.id().unwrap(), &DataCap::from(1));
         h.add_client(&rt, &VERIFIER, &client_pubkey, &allowance_client).unwrap();
 
         // Adding another client with the same address increments
         // the data cap which has already been granted.
         h.add_verifier(&rt, &VERIFIER, &allowance_verifier).unwrap();
+        expect_verifier_balance_event(&rt, VERIFIER.id().unwrap(), &DataCap::from(1));
         h.add_client(&rt, &VERIFIER, &CLIENT, &allowance_client).unwrap();
         h.check_state(&rt);
     }
@@ -329,6 +338,7 @@ mod clients {
         h.add_verifier(&rt, &VERIFIER, &allowance_verifier).unwrap();
 
         let allowance = rt.policy.minimum_verified_allocation_size.clone();
+        expect_verifier_balance_event(&rt, VERIFIER.id().unwrap(), &DataCap::from(42));
         h.add_client(&rt, &VERIFIER, &CLIENT, &allowance).unwrap();
         h.check_state(&rt);
     }
@@ -436,6 +446,11 @@ mod clients {
             None,
             ExitCode::OK,
         );
+        expect_verifier_balance_event(
+            &rt,
+            VERIFIER.id().unwrap(),
+            &(allowance_verifier - &allowance_client),
+        );
 
         rt.expect_validate_caller_any();
         rt.call::<VerifregActor>(
@@ -563,6 +578,7 @@ mod allocs_claims {
 
         // Remove the first alloc, which expired.
         rt.set_epoch(100);
+        expect_claim_event(&rt, "allocation-removed", id1, CLIENT1, PROVIDER1);
         let ret =
             h.remove_expired_allocations(&rt, CLIENT1, vec![id1, id2], alloc1.size.0).unwrap();
         assert_eq!(vec![1, 2], ret.considered);
@@ -571,6 +587,7 @@ mod allocs_claims {
 
         // Remove the second alloc (the first is no longer found).
         rt.set_epoch(200);
+        expect_claim_event(&rt, "allocation-removed", id2, CLIENT1, PROVIDER1);
         let ret =
             h.remove_expired_allocations(&rt, CLIENT1, vec![id1, id2], alloc2.size.0).unwrap();
         assert_eq!(vec![1, 2], ret.considered);
@@ -579,6 +596,8 @@ mod allocs_claims {
 
         // Reset state and show we can remove two at once.
         rt.replace_state(&state_with_allocs);
+        expect_claim_event(&rt, "allocation-removed", id1, CLIENT1, PROVIDER1);
+        expect_claim_event(&rt, "allocation-removed", id2, CLIENT1, PROVIDER1);
         let ret = h.remove_expired_allocations(&rt, CLIENT1, vec![id1, id2], total_size).unwrap();
         assert_eq!(vec![1, 2], ret.considered);
         assert_eq!(vec![ExitCode::OK, ExitCode::OK], ret.results.codes());
@@ -586,6 +605,7 @@ mod allocs_claims {
 
         // Reset state and show that only what was asked for is removed.
         rt.replace_state(&state_with_allocs);
+        expect_claim_event(&rt, "allocation-removed", id1, CLIENT1, PROVIDER1);
         let ret = h.remove_expired_allocations(&rt, CLIENT1, vec![id1], alloc1.size.0).unwrap();
         assert_eq!(vec![1], ret.considered);
         assert_eq!(vec![ExitCode::OK], ret.results.codes());
@@ -602,6 +622,7 @@ mod allocs_claims {
         assert!(h.load_alloc(&rt, CLIENT1, id2).is_some());
 
         rt.set_epoch(100);
+        expect_claim_event(&rt, "allocation-removed", id1, CLIENT1, PROVIDER1);
         let ret = h.remove_expired_allocations(&rt, CLIENT1, vec![], alloc1.size.0).unwrap();
         assert_eq!(vec![1], ret.considered);
         assert_eq!(vec![ExitCode::OK], ret.results.codes());
@@ -610,6 +631,7 @@ mod allocs_claims {
         assert!(h.load_alloc(&rt, CLIENT1, id2).is_some());
 
         rt.set_epoch(200);
+        expect_claim_event(&rt, "allocation-removed", id2, CLIENT1, PROVIDER1);
         let ret = h.remove_expired_allocations(&rt, CLIENT1, vec![], alloc2.size.0).unwrap();
         assert_eq!(vec![2], ret.considered);
         assert_eq!(vec![ExitCode::OK], ret.results.codes());
@@ -619,6 +641,8 @@ mod allocs_claims {
 
         // Reset state and show that specifying none removes *all* expired allocations
         rt.replace_state(&state_with_allocs);
+        expect_claim_event(&rt, "allocation-removed", id1, CLIENT1, PROVIDER1);
+        expect_claim_event(&rt, "allocation-removed", id2, CLIENT1, PROVIDER1);
         let ret = h.remove_expired_allocations(&rt, CLIENT1, vec![], total_size).unwrap();
         assert_eq!(vec![1, 2], ret.considered);
         assert_eq!(vec![ExitCode::OK, ExitCode::OK], ret.results.codes());
@@ -652,6 +676,8 @@ mod allocs_claims {
                 make_claim_req(1, &alloc1, sector, expiry),
                 make_claim_req(2, &alloc2, sector, expiry),
             ];
+            expect_claim_event(&rt, "claim", 1, CLIENT1, PROVIDER1);
+            expect_claim_event(&rt, "claim", 2, CLIENT2, PROVIDER1);
             let ret = h.claim_allocations(&rt, PROVIDER1, reqs, size * 2, false).unwrap();
             assert_eq!(ret.claim_results.len(), 2);
             assert_eq!(total_claimed_space(&ret), BigInt::from(2 * size));
@@ -667,6 +693,7 @@ mod allocs_claims {
                 make_claim_req(2, &alloc2, sector, expiry),
             ];
             reqs[1].client = CLIENT1;
+            expect_claim_event(&rt, "claim", 1, CLIENT1, PROVIDER1);
             let ret = h.claim_allocations(&rt, PROVIDER1, reqs, size, false).unwrap();
             assert_eq!(ret.claim_results.len(), 2);
             assert!(!ret.claim_results[0].claimed_space.is_zero());
@@ -683,6 +710,7 @@ mod allocs_claims {
                 make_claim_req(2, &alloc2, sector, expiry),
                 make_claim_req(3, &alloc3, sector, expiry), // Different provider
             ];
+            expect_claim_event(&rt, "claim", 2, CLIENT2, PROVIDER1);
             let ret = h.claim_allocations(&rt, PROVIDER1, reqs, size, false).unwrap();
             assert_eq!(ret.claim_results.len(), 2);
             assert!(!ret.claim_results[0].claimed_space.is_zero());
@@ -805,6 +833,9 @@ mod allocs_claims {
             ],
         };
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(CLIENT1));
+        expect_claim_event(&rt, "claim-updated", id1, CLIENT1, PROVIDER1);
+        expect_claim_event(&rt, "claim-updated", id2, CLIENT1, PROVIDER1);
+        expect_claim_event(&rt, "claim-updated", id3, CLIENT1, PROVIDER2);
         let ret = h.extend_claim_terms(&rt, &params).unwrap();
         assert_eq!(ret.codes(), vec![ExitCode::OK, ExitCode::OK, ExitCode::OK]);
 
@@ -833,6 +864,7 @@ mod allocs_claims {
                 terms: vec![ClaimTerm { provider: PROVIDER1, claim_id, term_max: max_term }],
             };
             rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(CLIENT1));
+            expect_claim_event(&rt, "claim-updated", claim_id, CLIENT1, PROVIDER1);
             let ret = h.extend_claim_terms(&rt, &params).unwrap();
             assert_eq!(ret.codes(), vec![ExitCode::OK]);
             rt.verify()
@@ -897,6 +929,7 @@ mod allocs_claims {
             };
             rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(CLIENT1));
             rt.set_epoch(max_term + 1);
+            expect_claim_event(&rt, "claim-updated", claim_id, CLIENT1, PROVIDER1);
             let ret = h.extend_claim_terms(&rt, &params).unwrap();
             assert_eq!(ret.codes(), vec![ExitCode::OK]);
             rt.verify()
@@ -950,12 +983,14 @@ mod allocs_claims {
 
         // One expired
         rt.set_epoch(term_start + term_min + 100);
+        expect_claim_event(&rt, "claim-removed", id1, CLIENT1, PROVIDER1);
         let ret = h.remove_expired_claims(&rt, PROVIDER1, vec![id1, id2]).unwrap();
         assert_eq!(vec![1, 2], ret.considered);
         assert_eq!(vec![ExitCode::OK, ExitCode::USR_FORBIDDEN], ret.results.codes());
 
         // Both now expired
         rt.set_epoch(term_start + term_min + 200);
+        expect_claim_event(&rt, "claim-removed", id2, CLIENT1, PROVIDER1);
         let ret = h.remove_expired_claims(&rt, PROVIDER1, vec![id1, id2]).unwrap();
         assert_eq!(vec![1, 2], ret.considered);
         assert_eq!(vec![ExitCode::USR_NOT_FOUND, ExitCode::OK], ret.results.codes());
@@ -970,6 +1005,8 @@ mod allocs_claims {
         assert!(h.load_claim(&rt, PROVIDER1, id2).is_some());
 
         rt.set_epoch(term_start + term_min + 200);
+        expect_claim_event(&rt, "claim-removed", id1, CLIENT1, PROVIDER1);
+        expect_claim_event(&rt, "claim-removed", id2, CLIENT1, PROVIDER1);
         let ret = h.remove_expired_claims(&rt, PROVIDER1, vec![]).unwrap();
         assert_eq!(vec![1, 2], ret.considered);
         assert_eq!(vec![ExitCode::OK, ExitCode::OK], ret.results.codes());
@@ -1010,6 +1047,7 @@ mod allocs_claims {
         // can call the exported method num
 
         rt.expect_validate_caller_any();
+        expect_claim_event(&rt, "claim-updated", id1, CLIENT1, PROVIDER1);
         let ret: ExtendClaimTermsReturn = rt
             .call::<Actor>(
                 Method::ExtendClaimTermsExported as MethodNum,
@@ -1103,6 +1141,8 @@ mod datacap {
                 make_alloc_req(&rt, PROVIDER2, SIZE * 2),
             ];
             let payload = make_receiver_hook_token_payload(CLIENT1, reqs.clone(), vec![], SIZE * 3);
+            expect_claim_event(&rt, "allocation", 1, CLIENT1, PROVIDER1);
+            expect_claim_event(&rt, "allocation", 2, CLIENT1, PROVIDER2);
             h.receive_tokens(&rt, payload, BatchReturn::ok(2), BATCH_EMPTY, vec![1, 2], 0).unwrap();
 
             // Verify allocations in state.
@@ -1115,6 +1155,7 @@ mod datacap {
             // Make another allocation from a different client
             let reqs = vec![make_alloc_req(&rt, PROVIDER1, SIZE)];
             let payload = make_receiver_hook_token_payload(CLIENT2, reqs.clone(), vec![], SIZE);
+            expect_claim_event(&rt, "allocation", 3, CLIENT2, PROVIDER1);
             h.receive_tokens(&rt, payload, BatchReturn::ok(1), BATCH_EMPTY, vec![3], 0).unwrap();
 
             // Verify allocations in state.
@@ -1148,6 +1189,8 @@ mod datacap {
         ];
         // Client1 extends both claims
         let payload = make_receiver_hook_token_payload(CLIENT1, vec![], reqs, SIZE * 3);
+        expect_claim_event(&rt, "claim-updated", cid1, CLIENT1, PROVIDER1);
+        expect_claim_event(&rt, "claim-updated", cid2, CLIENT2, PROVIDER2);
         h.receive_tokens(&rt, payload, BATCH_EMPTY, BatchReturn::ok(2), vec![], SIZE * 3).unwrap();
 
         // Verify claims in state.
@@ -1185,6 +1228,10 @@ mod datacap {
         // CLIENT1 makes two new allocations and extends two existing claims.
         let payload =
             make_receiver_hook_token_payload(CLIENT1, alloc_reqs.clone(), ext_reqs, SIZE * 6);
+        expect_claim_event(&rt, "allocation", 3, CLIENT1, PROVIDER1);
+        expect_claim_event(&rt, "allocation", 4, CLIENT1, PROVIDER2);
+        expect_claim_event(&rt, "claim-updated", cid1, CLIENT1, PROVIDER1);
+        expect_claim_event(&rt, "claim-updated", cid2, CLIENT2, PROVIDER2);
         h.receive_tokens(
             &rt,
             payload,
@@ -1407,6 +1454,7 @@ mod datacap {
             // But just on the limit is allowed
             let reqs = vec![make_extension_req(PROVIDER1, cid1, max_allowed_term)];
             let payload = make_receiver_hook_token_payload(CLIENT1, vec![], reqs, SIZE);
+            expect_claim_event(&rt, "claim-updated", cid1, CLIENT1, PROVIDER1);
             h.receive_tokens(&rt, payload, BATCH_EMPTY, BatchReturn::ok(1), vec![], SIZE).unwrap();
             h.check_state(&rt);
         }
@@ -1429,6 +1477,7 @@ mod datacap {
             rt.set_epoch(epoch);
             let reqs = vec![make_extension_req(PROVIDER1, cid1, new_term)];
             let payload = make_receiver_hook_token_payload(CLIENT1, vec![], reqs, SIZE);
+            expect_claim_event(&rt, "claim-updated", cid1, CLIENT1, PROVIDER1);
             h.receive_tokens(&rt, payload, BATCH_EMPTY, BatchReturn::ok(1), vec![], SIZE).unwrap();
             h.check_state(&rt);
         }
@@ -1454,6 +1503,7 @@ mod datacap {
             // But extension by just 1 epoch is allowed
             let reqs = vec![make_extension_req(PROVIDER1, cid1, term_max + 1)];
             let payload = make_receiver_hook_token_payload(CLIENT1, vec![], reqs, SIZE);
+            expect_claim_event(&rt, "claim-updated", cid1, CLIENT1, PROVIDER1);
             h.receive_tokens(&rt, payload, BATCH_EMPTY, BatchReturn::ok(1), vec![], SIZE).unwrap();
             h.check_state(&rt);
         }
@@ -2,6 +2,7 @@ use fil_actor_bundler::Bundler;
 use fil_actors_runtime::runtime::builtins::Type;
 use num_traits::cast::FromPrimitive;
 use std::error::Error;
+use std::ffi::OsStr;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 use std::process::{Command, Stdio};
@@ -32,9 +33,28 @@ const ACTORS: &[(&Package, &ID)] = &[
     ("ethaccount", "ethaccount"),
 ];
 
+/// Networks for which a bundle can be built. Only the configured network's is built, unless the
+/// `all-networks` feature is enabled.
+/// These must be kept in sync with the `Network` enum in src/lib.rs.
+const NETWORKS: &[&str] = &[
+    "mainnet",
+    "caterpillarnet",
+    "butterflynet",
+    "calibrationnet",
+    "devnet",
+    "testing",
+    "testing-fake-proofs",
+];
+
 const NETWORK_ENV: &str = "BUILD_FIL_NETWORK";
 
+/// C toolchain used to build the C dependencies of the actors (blst) for Wasm, with the default
+/// used when unset. GCC can't target Wasm, so this defaults to clang and LLVM's archiver.
+const WASM_C_TOOLCHAIN: &[(&str, &str)] =
+    &[("CC_wasm32_unknown_unknown", "clang"), ("AR_wasm32_unknown_unknown", "llvm-ar")];
+
 /// Returns the configured network name, checking both the environment and feature flags.
+/// This is the network of the default bundle.
 fn network_name() -> String {
     let env_network = std::env::var_os(NETWORK_ENV);
 
@@ -81,21 +101,22 @@ fn main() -> Result<(), Box<dyn Error>> {
         .expect("no OUT_DIR env var");
     println!("cargo:warning=out_dir: {:?}", &out_dir);
 
-    // Compute the package names.
-    let packages =
-        ACTORS.iter().map(|(pkg, _)| String::from("fil_actor_") + pkg).collect::<Vec<String>>();
-
     let manifest_path =
         Path::new(&std::env::var_os("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR unset"))
             .join("Cargo.toml");
     println!("cargo:warning=manifest_path={:?}", &manifest_path);
 
-    // Determine the network name.
+    // Determine the network name of the default bundle.
     let network_name = network_name();
     println!("cargo:warning=network name: {}", network_name);
+    assert!(NETWORKS.contains(&network_name.as_str()), "unknown network {}", network_name);
+    println!("cargo:rustc-cfg=network=\"{}\"", network_name);
 
-    // Make sure we re-build if the network name changes.
+    // Make sure we re-build if the network name or C toolchain changes.
     println!("cargo:rerun-if-env-changed={}", NETWORK_ENV);
+    for (var, _) in WASM_C_TOOLCHAIN {
+        println!("cargo:rerun-if-env-changed={}", var);
+    }
 
     // Rerun if the source, dependencies, build options, build script _or_ actors have changed. We
     // need to check if the actors have changed because otherwise, when building in a workspace, we
@@ -107,8 +128,36 @@ fn main() -> Result<(), Box<dyn Error>> {
         println!("cargo:rerun-if-changed={}", file);
     }
 
+    let networks = if cfg!(feature = "all-networks") {
+        NETWORKS.to_vec()
+    } else {
+        vec![network_name.as_str()]
+    };
+    for network in networks {
+        // Each network is built in its own target directory, as the actors are compiled
+        // differently for each and would otherwise invalidate each other's artifacts.
+        let target_dir = out_dir.join(network);
+        build_actors(&cargo, &manifest_path, &target_dir, network)?;
+        write_bundle(&target_dir, &out_dir.join(format!("{}.car", network)))?;
+        println!("cargo:rustc-cfg=bundle=\"{}\"", network);
+    }
+
+    Ok(())
+}
+
+/// Builds all actors to Wasm for a network.
+fn build_actors(
+    cargo: &OsStr,
+    manifest_path: &Path,
+    target_dir: &Path,
+    network_name: &str,
+) -> Result<(), Box<dyn Error>> {
+    // Compute the package names.
+    let packages =
+        ACTORS.iter().map(|(pkg, _)| String::from("fil_actor_") + pkg).collect::<Vec<String>>();
+
     // Cargo build command for all actors at once.
-    let mut cmd = Command::new(&cargo);
+    let mut cmd = Command::new(cargo);
     cmd.arg("build")
         .args(packages.iter().map(|pkg| "-p=".to_owned() + pkg))
         .arg("--target=wasm32-unknown-unknown")
@@ -117,11 +166,16 @@ fn main() -> Result<(), Box<dyn Error>> {
         .arg("--features=fil-actor")
         .arg("--manifest-path=".to_owned() + manifest_path.to_str().unwrap())
         .env(NETWORK_ENV, network_name)
+        .envs(
+            WASM_C_TOOLCHAIN.iter().map(|&(var, default)| {
+                (var, std::env::var_os(var).unwrap_or_else(|| default.into()))
+            }),
+        )
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         // We are supposed to only generate artifacts under OUT_DIR,
-        // so set OUT_DIR as the target directory for this build.
-        .env("CARGO_TARGET_DIR", &out_dir)
+        // so set a directory there as the target directory for this build.
+        .env("CARGO_TARGET_DIR", target_dir)
         // As we are being called inside a build-script, this env variable is set. However, we set
         // our own `RUSTFLAGS` and thus, we need to remove this. Otherwise cargo favors this
         // env variable.
@@ -156,17 +210,20 @@ fn main() -> Result<(), Box<dyn Error>> {
         return Err("actor build failed".into());
     }
 
-    let dst = Path::new(&out_dir).join("bundle.car");
-    let mut bundler = Bundler::new(&dst);
+    Ok(())
+}
+
+/// Writes a bundle of the actors built in a target directory.
+fn write_bundle(target_dir: &Path, dst: &Path) -> Result<(), Box<dyn Error>> {
+    let mut bundler = Bundler::new(dst);
     for (&(pkg, name), id) in ACTORS.iter().zip(1u32..) {
         assert_eq!(
             name,
             Type::from_u32(id).expect("type not defined").name(),
             "actor types don't match actors included in the bundle"
         );
-        let bytecode_path = Path::new(&out_dir)
-            .join("wasm32-unknown-unknown/wasm")
-            .join(format!("fil_actor_{}.wasm", pkg));
+        let bytecode_path =
+            target_dir.join("wasm32-unknown-unknown/wasm").join(format!("fil_actor_{}.wasm", pkg));
 
         // This actor version doesn't force synthetic CIDs; it uses genuine
         // content-addressed CIDs.
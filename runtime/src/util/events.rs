@@ -0,0 +1,76 @@
+use fvm_ipld_encoding::CBOR;
+use fvm_shared::event::{ActorEvent, Entry, Flags};
+use serde::ser;
+
+use crate::cbor::serialize_vec;
+use crate::ActorError;
+
+/// The key of the entry carrying an event's type name.
+pub const EVENT_TYPE_KEY: &str = "$type";
+
+/// Builds an ActorEvent from a sequence of key/value entries.
+/// Values are serialized as CBOR. The first serialization failure is retained and
+/// returned from `build`, so a chain of calls need only check for errors once at the end.
+#[derive(Debug)]
+pub struct EventBuilder {
+    entries: Result<Vec<Entry>, ActorError>,
+}
+
+impl EventBuilder {
+    /// Creates a new builder with no entries.
+    pub fn new() -> Self {
+        Self { entries: Ok(Vec::new()) }
+    }
+
+    /// Pushes the event type entry, e.g. "deal-activated".
+    /// The type is indexed by both key and value.
+    pub fn typ(self, typ: &str) -> Self {
+        self.push_entry(EVENT_TYPE_KEY, typ, Flags::FLAG_INDEXED_ALL)
+    }
+
+    /// Pushes an entry with an indexed key and an un-indexed, CBOR-serialized value.
+    pub fn field<T: ser::Serialize + ?Sized>(self, name: &str, value: &T) -> Self {
+        self.push_entry(name, value, Flags::FLAG_INDEXED_KEY)
+    }
+
+    /// Pushes an entry with an indexed key and an indexed, CBOR-serialized value.
+    pub fn field_indexed<T: ser::Serialize + ?Sized>(self, name: &str, value: &T) -> Self {
+        self.push_entry(name, value, Flags::FLAG_INDEXED_ALL)
+    }
+
+    /// Pushes an entry with a CBOR-serialized value and explicit indexing flags.
+    pub fn field_with_flags<T: ser::Serialize + ?Sized>(
+        self,
+        name: &str,
+        value: &T,
+        flags: Flags,
+    ) -> Self {
+        self.push_entry(name, value, flags)
+    }
+
+    /// Returns the accumulated event, or the first error encountered while building it.
+    pub fn build(self) -> Result<ActorEvent, ActorError> {
+        Ok(ActorEvent { entries: self.entries? })
+    }
+
+    fn push_entry<T: ser::Serialize + ?Sized>(
+        mut self,
+        key: &str,
+        value: &T,
+        flags: Flags,
+    ) -> Self {
+        if let Ok(entries) = &mut self.entries {
+            match serialize_vec(value, "event value") {
+                Ok(value) => entries.push(Entry { flags, key: key.to_owned(), codec: CBOR, value }),
+                Err(e) => self.entries = Err(e),
+            }
+        }
+        self
+    }
+}
+
+impl Default for EventBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
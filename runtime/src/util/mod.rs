@@ -5,6 +5,7 @@ pub use self::batch_return::BatchReturn;
 pub use self::batch_return::BatchReturnGen;
 pub use self::batch_return::FailCode;
 pub use self::downcast::*;
+pub use self::events::EventBuilder;
 pub use self::mapmap::MapMap;
 pub use self::message_accumulator::MessageAccumulator;
 pub use self::multimap::*;
@@ -14,6 +15,7 @@ pub use self::set_multimap::SetMultimap;
 mod batch_return;
 pub mod cbor;
 mod downcast;
+pub mod events;
 mod mapmap;
 mod message_accumulator;
 mod multimap;
@@ -0,0 +1,54 @@
+use fil_actors_runtime::events::EVENT_TYPE_KEY;
+use fil_actors_runtime::EventBuilder;
+use fvm_ipld_encoding::{to_vec, CBOR};
+use fvm_shared::address::Address;
+use fvm_shared::event::{ActorEvent, Entry, Flags};
+
+#[test]
+fn builds_typed_event() {
+    let client = Address::new_id(1000);
+    let event = EventBuilder::new()
+        .typ("deal-published")
+        .field_indexed("id", &42u64)
+        .field("client", &client)
+        .field_with_flags("note", "hello", Flags::empty())
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        ActorEvent {
+            entries: vec![
+                Entry {
+                    flags: Flags::FLAG_INDEXED_ALL,
+                    key: EVENT_TYPE_KEY.to_string(),
+                    codec: CBOR,
+                    value: to_vec("deal-published").unwrap(),
+                },
+                Entry {
+                    flags: Flags::FLAG_INDEXED_ALL,
+                    key: "id".to_string(),
+                    codec: CBOR,
+                    value: to_vec(&42u64).unwrap(),
+                },
+                Entry {
+                    flags: Flags::FLAG_INDEXED_KEY,
+                    key: "client".to_string(),
+                    codec: CBOR,
+                    value: to_vec(&client).unwrap(),
+                },
+                Entry {
+                    flags: Flags::empty(),
+                    key: "note".to_string(),
+                    codec: CBOR,
+                    value: to_vec("hello").unwrap(),
+                },
+            ]
+        },
+        event
+    );
+}
+
+#[test]
+fn empty_builder() {
+    assert_eq!(ActorEvent { entries: vec![] }, EventBuilder::new().build().unwrap());
+}
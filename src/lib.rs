@@ -24,4 +24,109 @@
 ///
 /// The Filecoin client must import the contents of CAR into the blockstore, but
 /// may opt to exclude the index data structure.
-pub const BUNDLE_CAR: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/bundle/bundle.car"));
+///
+/// This is the bundle for the network configured at build time, through a feature or the
+/// `BUILD_FIL_NETWORK` environment variable. With the `all-networks` feature, bundles for every
+/// network are available from [`bundle_for`].
+pub const BUNDLE_CAR: &[u8] = match bundle_for(NETWORK) {
+    Some(car) => car,
+    None => panic!("no bundle built for the configured network"),
+};
+
+/// The network configured at build time, which defaults to mainnet.
+pub const NETWORK: Network = if cfg!(network = "caterpillarnet") {
+    Network::Caterpillarnet
+} else if cfg!(network = "butterflynet") {
+    Network::Butterflynet
+} else if cfg!(network = "calibrationnet") {
+    Network::Calibrationnet
+} else if cfg!(network = "devnet") {
+    Network::Devnet
+} else if cfg!(network = "testing") {
+    Network::Testing
+} else if cfg!(network = "testing-fake-proofs") {
+    Network::TestingFakeProofs
+} else {
+    Network::Mainnet
+};
+
+/// A network for which actors are built, with network-specific parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Network {
+    Mainnet,
+    Caterpillarnet,
+    Butterflynet,
+    Calibrationnet,
+    Devnet,
+    Testing,
+    TestingFakeProofs,
+}
+
+impl Network {
+    /// All networks, each of which has a bundle.
+    pub const ALL: &'static [Network] = &[
+        Network::Mainnet,
+        Network::Caterpillarnet,
+        Network::Butterflynet,
+        Network::Calibrationnet,
+        Network::Devnet,
+        Network::Testing,
+        Network::TestingFakeProofs,
+    ];
+
+    /// The network's name, as accepted by `BUILD_FIL_NETWORK`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Caterpillarnet => "caterpillarnet",
+            Network::Butterflynet => "butterflynet",
+            Network::Calibrationnet => "calibrationnet",
+            Network::Devnet => "devnet",
+            Network::Testing => "testing",
+            Network::TestingFakeProofs => "testing-fake-proofs",
+        }
+    }
+}
+
+impl std::fmt::Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Network {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Network::ALL
+            .iter()
+            .find(|n| n.name() == s)
+            .copied()
+            .ok_or_else(|| format!("unknown network {}", s))
+    }
+}
+
+macro_rules! bundle {
+    ($name:literal) => {{
+        #[cfg(bundle = $name)]
+        let car: Option<&'static [u8]> =
+            Some(include_bytes!(concat!(env!("OUT_DIR"), "/bundle/", $name, ".car")));
+        #[cfg(not(bundle = $name))]
+        let car = None;
+        car
+    }};
+}
+
+/// Returns the bundled CAR for a network, in the same format as [`BUNDLE_CAR`].
+/// Only the configured network's bundle is built, unless the `all-networks` feature is enabled.
+pub const fn bundle_for(network: Network) -> Option<&'static [u8]> {
+    match network {
+        Network::Mainnet => bundle!("mainnet"),
+        Network::Caterpillarnet => bundle!("caterpillarnet"),
+        Network::Butterflynet => bundle!("butterflynet"),
+        Network::Calibrationnet => bundle!("calibrationnet"),
+        Network::Devnet => bundle!("devnet"),
+        Network::Testing => bundle!("testing"),
+        Network::TestingFakeProofs => bundle!("testing-fake-proofs"),
+    }
+}
@@ -1,7 +1,12 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::io::Write;
+use std::path::PathBuf;
 
-use fil_builtin_actors_bundle::BUNDLE_CAR;
+use fil_builtin_actors_bundle::{bundle_for, Network, NETWORK};
+
+use crate::manifest::{diff, Bundle, Change};
+
+mod manifest;
 
 #[derive(Parser)]
 #[clap(name = env!("CARGO_PKG_NAME"))]
@@ -11,12 +16,89 @@ struct Cli {
     /// The output car path. Defaults to STDOUT.
     #[clap(short, long, required = false)]
     output: Option<String>,
+
+    /// The network whose bundle is written or inspected. Defaults to the network configured at
+    /// build time.
+    #[clap(short, long, global = true)]
+    network: Option<Network>,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
 }
 
-fn main() -> Result<(), std::io::Error> {
+#[derive(Subcommand)]
+enum Command {
+    /// Prints a bundle's manifest version, and the type, code CID and Wasm size of each actor.
+    Inspect {
+        /// Path to a bundle CAR. Defaults to the embedded bundle for the network.
+        bundle: Option<PathBuf>,
+    },
+    /// Prints the name and code CID of each actor in a bundle, one per line.
+    List {
+        /// Path to a bundle CAR. Defaults to the embedded bundle for the network.
+        bundle: Option<PathBuf>,
+    },
+    /// Prints the actors added, removed or changed between two bundles.
+    Diff {
+        /// Path to the old bundle CAR.
+        old: PathBuf,
+        /// Path to the new bundle CAR. Defaults to the embedded bundle for the network.
+        new: Option<PathBuf>,
+    },
+}
+
+fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
-    match cli.output {
-        Some(path) => std::fs::write(path, BUNDLE_CAR),
-        None => std::io::stdout().write_all(BUNDLE_CAR),
+    let network = cli.network.unwrap_or(NETWORK);
+    let car = bundle_for(network).ok_or_else(|| {
+        anyhow::anyhow!("no bundle built for {}; build with the all-networks feature", network)
+    })?;
+    match cli.command {
+        None => match cli.output {
+            Some(path) => std::fs::write(path, car)?,
+            None => std::io::stdout().write_all(car)?,
+        },
+        Some(Command::Inspect { bundle }) => {
+            let bundle = load(bundle, car)?;
+            println!("manifest: {}", bundle.root);
+            println!("version: {}", bundle.version);
+            for actor in &bundle.actors {
+                let typ = actor.typ.map(|t| format!("{:?}", t)).unwrap_or_else(|| "?".into());
+                println!("{:<18} {:<18} {} {:>9}", typ, actor.name, actor.code, actor.wasm_size);
+            }
+        }
+        Some(Command::List { bundle }) => {
+            for actor in &load(bundle, car)?.actors {
+                println!("{} {}", actor.name, actor.code);
+            }
+        }
+        Some(Command::Diff { old, new }) => {
+            let old = load(Some(old), car)?;
+            let new = load(new, car)?;
+            if old.version != new.version {
+                println!("~ version {} -> {}", old.version, new.version);
+            }
+            for change in diff(&old, &new) {
+                match change {
+                    Change::Added(a) => println!("+ {} {} ({} bytes)", a.name, a.code, a.wasm_size),
+                    Change::Removed(a) => {
+                        println!("- {} {} ({} bytes)", a.name, a.code, a.wasm_size)
+                    }
+                    Change::Changed { old, new } => println!(
+                        "~ {} {} -> {} ({} -> {} bytes)",
+                        old.name, old.code, new.code, old.wasm_size, new.wasm_size
+                    ),
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Loads the bundle at a path, or the given embedded bundle if none is given.
+fn load(path: Option<PathBuf>, embedded: &[u8]) -> anyhow::Result<Bundle> {
+    match path {
+        Some(path) => Bundle::parse(&std::fs::read(path)?),
+        None => Bundle::parse(embedded),
     }
 }
@@ -0,0 +1,125 @@
+use anyhow::{anyhow, Context};
+use cid::Cid;
+use fil_actors_runtime::runtime::builtins::Type;
+use fvm_ipld_blockstore::{Blockstore, MemoryBlockstore};
+use fvm_ipld_encoding::CborStore;
+use num_traits::FromPrimitive;
+
+/// The only manifest version understood.
+const MANIFEST_VERSION: u32 = 1;
+
+/// An actor listed in a bundle's manifest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Actor {
+    /// The name under which the actor is listed, e.g. "storageminer".
+    pub name: String,
+    /// The builtin actor type with that name, if known to this version.
+    pub typ: Option<Type>,
+    pub code: Cid,
+    /// Size of the actor's Wasm bytecode, in bytes.
+    pub wasm_size: usize,
+}
+
+/// A bundle of actor code, as parsed from its CAR.
+#[derive(Clone, Debug)]
+pub struct Bundle {
+    /// The manifest CID, which is the root of the CAR.
+    pub root: Cid,
+    pub version: u32,
+    pub actors: Vec<Actor>,
+}
+
+impl Bundle {
+    /// Parses a CARv1 bundle, whose single root is the manifest.
+    pub fn parse(car: &[u8]) -> anyhow::Result<Self> {
+        let store = MemoryBlockstore::new();
+        let roots = futures::executor::block_on(fvm_ipld_car::load_car_unchecked(&store, car))
+            .context("failed to load bundle CAR")?;
+        let root = match roots.as_slice() {
+            [root] => *root,
+            _ => return Err(anyhow!("expected one root in bundle CAR, found {}", roots.len())),
+        };
+
+        let (version, data): (u32, Cid) =
+            store.get_cbor(&root)?.ok_or_else(|| anyhow!("manifest {} not found", root))?;
+        if version != MANIFEST_VERSION {
+            return Err(anyhow!("unsupported manifest version {}", version));
+        }
+        let entries: Vec<(String, Cid)> =
+            store.get_cbor(&data)?.ok_or_else(|| anyhow!("manifest data {} not found", data))?;
+
+        let actors = entries
+            .into_iter()
+            .map(|(name, code)| {
+                let wasm_size = store
+                    .get(&code)?
+                    .ok_or_else(|| anyhow!("code {} for actor {} not found", code, name))?
+                    .len();
+                Ok(Actor { typ: type_by_name(&name), name, code, wasm_size })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Bundle { root, version, actors })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Actor> {
+        self.actors.iter().find(|a| a.name == name)
+    }
+}
+
+/// A difference between the actors of two bundles.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change<'a> {
+    Added(&'a Actor),
+    Removed(&'a Actor),
+    Changed { old: &'a Actor, new: &'a Actor },
+}
+
+/// Lists the actors added, removed or with changed code between two bundles,
+/// in the order of the old bundle's manifest followed by any additions.
+pub fn diff<'a>(old: &'a Bundle, new: &'a Bundle) -> Vec<Change<'a>> {
+    let mut changes = Vec::new();
+    for old_actor in &old.actors {
+        match new.get(&old_actor.name) {
+            Some(new_actor) if new_actor.code != old_actor.code => {
+                changes.push(Change::Changed { old: old_actor, new: new_actor })
+            }
+            Some(_) => {}
+            None => changes.push(Change::Removed(old_actor)),
+        }
+    }
+    for new_actor in &new.actors {
+        if old.get(&new_actor.name).is_none() {
+            changes.push(Change::Added(new_actor));
+        }
+    }
+    changes
+}
+
+fn type_by_name(name: &str) -> Option<Type> {
+    (1..).map_while(Type::from_i32).find(|t| t.name() == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fil_builtin_actors_bundle::{bundle_for, Network, NETWORK};
+
+    #[test]
+    fn parse_embedded_bundles() {
+        let expected: Vec<_> = (1..).map_while(Type::from_i32).map(Some).collect();
+        // The configured network's bundle is always built; the others only with all-networks.
+        assert!(bundle_for(NETWORK).is_some());
+        for network in Network::ALL {
+            let car = match bundle_for(*network) {
+                Some(car) => car,
+                None => continue,
+            };
+            let bundle = Bundle::parse(car).unwrap();
+            assert_eq!(MANIFEST_VERSION, bundle.version);
+            let types: Vec<_> = bundle.actors.iter().map(|a| a.typ).collect();
+            assert_eq!(expected, types, "{}", network);
+            assert!(bundle.actors.iter().all(|a| a.wasm_size > 0));
+            assert!(diff(&bundle, &bundle).is_empty());
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::anyhow;
+use cid::Cid;
+use clap::Parser;
+use fil_actors_runtime::runtime::Policy;
+use fil_builtin_actors_state::check::{check_state_invariants, Tree};
+use fil_builtin_actors_state::snapshot::{
+    actors_root, load_manifest, state_for_tipset, CarBlockstore,
+};
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::TOTAL_FILECOIN;
+
+#[derive(Parser)]
+#[clap(name = "check-state")]
+#[clap(version = env!("CARGO_PKG_VERSION"))]
+#[clap(about = "Checks the invariants of all actors in a chain snapshot.", long_about = None)]
+struct Cli {
+    /// Path to the snapshot CAR, whose roots are the block headers of the tipset to check.
+    snapshot: PathBuf,
+
+    /// Check this state root instead of the one the snapshot's tipset was executed on.
+    #[clap(long, requires = "epoch")]
+    state_root: Option<Cid>,
+
+    /// The epoch at which the state root was computed. Required with --state-root.
+    #[clap(long)]
+    epoch: Option<ChainEpoch>,
+
+    /// The expected total balance of all actors, in whole FIL.
+    /// Defaults to the total supply of mainnet.
+    #[clap(long)]
+    total_fil: Option<u64>,
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let cli = Cli::parse();
+
+    // Snapshots are too large to hold in memory, so blocks are read from the file on demand.
+    let store = CarBlockstore::new(BufReader::new(File::open(&cli.snapshot)?))?;
+    let roots = store.roots();
+    let (actors, prior_epoch) = match (cli.state_root, cli.epoch) {
+        (Some(state_root), Some(epoch)) => (actors_root(&store, &state_root)?, epoch),
+        _ => {
+            let header = roots.first().ok_or_else(|| anyhow!("snapshot has no roots"))?;
+            state_for_tipset(&store, header)?
+        }
+    };
+    println!("checking state {} at epoch {}", actors, prior_epoch);
+
+    let tree = Tree::load(&store, &actors)?;
+    let manifest = load_manifest(&tree)?;
+    let total_fil =
+        cli.total_fil.map(TokenAmount::from_whole).unwrap_or_else(|| TOTAL_FILECOIN.clone());
+    let acc = check_state_invariants(&manifest, &Policy::default(), tree, &total_fil, prior_epoch)?;
+
+    // Messages about a single actor are prefixed with its address.
+    // Cross-actor and whole-tree findings are not.
+    let mut by_actor = BTreeMap::<String, Vec<String>>::new();
+    for msg in acc.messages() {
+        let (group, text) = match msg.split_once(' ') {
+            Some((addr, text)) if addr.parse::<Address>().is_ok() => (addr.to_string(), text),
+            _ => ("cross-actor".to_string(), msg.as_str()),
+        };
+        by_actor.entry(group).or_default().push(text.to_string());
+    }
+    for (group, msgs) in &by_actor {
+        println!("{} ({} findings)", group, msgs.len());
+        for msg in msgs {
+            println!("  {}", msg);
+        }
+    }
+
+    if acc.is_empty() {
+        println!("no invariant violations found");
+        Ok(ExitCode::SUCCESS)
+    } else {
+        println!("{} invariant violations found", acc.len());
+        Ok(ExitCode::FAILURE)
+    }
+}
@@ -7,6 +7,7 @@ use cid::Cid;
 use fil_actor_account::State as AccountState;
 use fil_actor_cron::State as CronState;
 use fil_actor_datacap::State as DataCapState;
+use fil_actor_evm::State as EvmState;
 use fil_actor_init::State as InitState;
 use fil_actor_market::State as MarketState;
 use fil_actor_miner::CronEventPayload;
@@ -23,6 +24,7 @@ use fil_actor_verifreg::{DataCap, State as VerifregState};
 
 use fil_actors_runtime::runtime::Policy;
 use fil_actors_runtime::VERIFIED_REGISTRY_ACTOR_ADDR;
+use fil_actors_runtime::{EAM_ACTOR_ADDR, EAM_ACTOR_ID};
 
 use fil_actors_runtime::Map;
 use fil_actors_runtime::MessageAccumulator;
@@ -30,10 +32,12 @@ use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::from_slice;
 use fvm_ipld_encoding::CborStore;
 use fvm_shared::address::Address;
+use fvm_shared::address::Payload;
 use fvm_shared::address::Protocol;
 
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::econ::TokenAmount;
+use fvm_shared::ActorID;
 use num_traits::Zero;
 
 use anyhow::anyhow;
@@ -42,6 +46,7 @@ use fvm_ipld_encoding::tuple::*;
 use fil_actor_account::testing as account;
 use fil_actor_cron::testing as cron;
 use fil_actor_datacap::testing as datacap;
+use fil_actor_evm::testing as evm;
 use fil_actor_init::testing as init;
 use fil_actor_market::testing as market;
 use fil_actor_miner::testing as miner;
@@ -70,6 +75,17 @@ pub struct Actor {
     pub address: Option<Address>,
 }
 
+/// The versioned root of a state tree, pointing to the actors HAMT.
+#[derive(Serialize_tuple, Deserialize_tuple, Clone, PartialEq, Eq, Debug)]
+pub struct StateRoot {
+    /// State tree version
+    pub version: u64,
+    /// Root of the actors HAMT
+    pub actors: Cid,
+    /// State info, reserved for future use
+    pub info: Cid,
+}
+
 /// A specialization of a map of ID-addresses to actor heads.
 pub struct Tree<'a, BS>
 where
@@ -134,6 +150,9 @@ pub fn check_state_invariants<BS: Blockstore>(
     let mut reward_summary: Option<reward::StateSummary> = None;
     let mut verifreg_summary: Option<verifreg::StateSummary> = None;
     let mut datacap_summary: Option<frc46_token::token::state::StateSummary> = None;
+    let mut evm_summaries = HashMap::<Address, evm::StateSummary>::new();
+    let mut delegated_addresses = HashMap::<Address, Address>::new();
+    let mut call_seq_nums = HashMap::<ActorID, u64>::new();
 
     tree.for_each(|key, actor| {
         let acc = acc.with_prefix(format!("{key} "));
@@ -142,6 +161,9 @@ pub fn check_state_invariants<BS: Blockstore>(
             acc.add(format!("unexpected address protocol in state tree root: {key}"));
         }
         total_fil += &actor.balance;
+        if let Ok(id) = key.id() {
+            call_seq_nums.insert(id, actor.call_seq_num);
+        }
 
         match manifest.get_by_left(&actor.code) {
             Some(Type::System) => (),
@@ -220,10 +242,28 @@ pub fn check_state_invariants<BS: Blockstore>(
                 acc.with_prefix("datacap: ").add_all(&msgs);
                 datacap_summary = Some(summary);
             }
-            Some(Type::Placeholder) => {}
-            Some(Type::EVM) => {}
-            Some(Type::EAM) => {}
-            Some(Type::EthAccount) => {}
+            Some(Type::Placeholder) => {
+                if let Some(address) = check_delegated_address(&acc, actor, None) {
+                    delegated_addresses.insert(*key, address);
+                }
+            }
+            Some(Type::EVM) => {
+                let state = get_state!(tree, actor, EvmState);
+                let (summary, msgs) = evm::check_state_invariants(&state, tree.store);
+                acc.with_prefix("evm: ").add_all(&msgs);
+                evm_summaries.insert(*key, summary);
+                if let Some(address) = check_delegated_address(&acc, actor, Some(EAM_ACTOR_ID)) {
+                    delegated_addresses.insert(*key, address);
+                }
+            }
+            Some(Type::EAM) => {
+                acc.require(*key == EAM_ACTOR_ADDR, "EAM actor at unexpected address");
+            }
+            Some(Type::EthAccount) => {
+                if let Some(address) = check_delegated_address(&acc, actor, Some(EAM_ACTOR_ID)) {
+                    delegated_addresses.insert(*key, address);
+                }
+            }
             None => {
                 bail!("unexpected actor code CID {} for address {}", actor.code, key);
             }
@@ -233,6 +273,12 @@ pub fn check_state_invariants<BS: Blockstore>(
     })?;
 
     // Perform cross-actor checks from state summaries here.
+    if let Some(init_summary) = init_summary {
+        check_delegated_addresses_against_init(&acc, &delegated_addresses, &init_summary);
+    }
+
+    check_evm_tombstones(&acc, &evm_summaries, &call_seq_nums);
+
     if let Some(power_summary) = power_summary {
         check_miner_against_power(&acc, &miner_summaries, &power_summary);
     }
@@ -553,3 +599,91 @@ fn check_verifreg_against_miners(
         );
     }
 }
+
+/// Checks that an actor has a delegated address, in the expected namespace if given.
+/// Returns the address if it is delegated.
+fn check_delegated_address(
+    acc: &MessageAccumulator,
+    actor: &Actor,
+    namespace: Option<ActorID>,
+) -> Option<Address> {
+    match actor.address.as_ref().map(|a| a.payload()) {
+        Some(Payload::Delegated(delegated)) => {
+            if let Some(namespace) = namespace {
+                acc.require(
+                    delegated.namespace() == namespace,
+                    format!(
+                        "delegated address {} not in namespace {namespace}",
+                        actor.address.unwrap()
+                    ),
+                );
+            }
+            actor.address
+        }
+        Some(_) => {
+            acc.add(format!("address {} is not a delegated address", actor.address.unwrap()));
+            None
+        }
+        None => {
+            acc.add("actor has no delegated address");
+            None
+        }
+    }
+}
+
+fn check_delegated_addresses_against_init(
+    acc: &MessageAccumulator,
+    delegated_addresses: &HashMap<Address, Address>,
+    init_summary: &init::StateSummary,
+) {
+    for (id_address, delegated) in delegated_addresses {
+        match init_summary.ids_by_address.get(delegated) {
+            Some(id) => acc.require(
+                Address::new_id(*id) == *id_address,
+                format!("{id_address} delegated address {delegated} maps to ID {id} in init actor"),
+            ),
+            None => acc
+                .add(format!("{id_address} delegated address {delegated} not found in init actor")),
+        }
+    }
+
+    // Every delegated address known to the init actor belongs to an actor that has it.
+    for (address, id) in &init_summary.ids_by_address {
+        if address.protocol() != Protocol::Delegated {
+            continue;
+        }
+        let id_address = Address::new_id(*id);
+        acc.require(
+            delegated_addresses.get(&id_address) == Some(address),
+            format!("init actor maps {address} to {id_address}, which has a different address"),
+        );
+    }
+}
+
+fn check_evm_tombstones(
+    acc: &MessageAccumulator,
+    evm_summaries: &HashMap<Address, evm::StateSummary>,
+    call_seq_nums: &HashMap<ActorID, u64>,
+) {
+    for (address, summary) in evm_summaries {
+        let tombstone = match &summary.tombstone {
+            Some(tombstone) => tombstone,
+            None => continue,
+        };
+        // The message that self-destructed the contract has been applied,
+        // so its origin's nonce has moved past it.
+        match call_seq_nums.get(&tombstone.origin) {
+            Some(call_seq_num) => acc.require(
+                *call_seq_num > tombstone.nonce,
+                format!(
+                    "{address} tombstone nonce {} is not less than origin {} nonce {call_seq_num}",
+                    tombstone.nonce, tombstone.origin
+                ),
+            ),
+            None => acc.add(format!(
+                "{address} tombstone origin {} not found in state tree",
+                tombstone.origin
+            )),
+        }
+    }
+}
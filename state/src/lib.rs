@@ -1 +1,4 @@
 pub mod check;
+pub mod migration;
+#[cfg(feature = "cli")]
+pub mod snapshot;
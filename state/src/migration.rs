@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use anyhow::{anyhow, Context};
+use bimap::BiBTreeMap;
+use cid::Cid;
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::{Map, MessageAccumulator};
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::CborStore;
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use multihash::Code;
+use num_traits::Zero;
+
+use crate::check::{check_state_invariants, Actor, StateRoot, Tree};
+
+/// Input to a single actor's state migration.
+#[derive(Clone, Debug)]
+pub struct ActorMigrationInput {
+    /// ID address of the actor being migrated.
+    pub address: Address,
+    /// Balance of the actor being migrated.
+    pub balance: TokenAmount,
+    /// Head state of the actor prior to migration.
+    pub head: Cid,
+    /// Epoch of the last tipset prior to the upgrade.
+    pub prior_epoch: ChainEpoch,
+    /// Cache shared between all migrators for the duration of a state tree migration.
+    pub cache: MigrationCache,
+}
+
+/// Result of a single actor's state migration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActorMigrationOutput {
+    /// Code CID of the actor after migration.
+    pub new_code_cid: Cid,
+    /// Head state of the actor after migration.
+    pub new_head: Cid,
+}
+
+/// Transforms the state of a single actor of some type to the state and code of the new version.
+/// Migrators are invoked concurrently on many actors, so must not depend on any ordering.
+pub trait ActorMigration<BS: Blockstore>: Send + Sync {
+    fn migrate_state(
+        &self,
+        store: &BS,
+        input: ActorMigrationInput,
+    ) -> anyhow::Result<ActorMigrationOutput>;
+}
+
+/// Migrator that updates an actor's code CID, leaving its state untouched.
+/// This is the migration for any actor type whose state schema didn't change.
+pub struct CodeMigrator {
+    new_code_cid: Cid,
+}
+
+impl CodeMigrator {
+    pub fn new(new_code_cid: Cid) -> Self {
+        Self { new_code_cid }
+    }
+}
+
+impl<BS: Blockstore> ActorMigration<BS> for CodeMigrator {
+    fn migrate_state(
+        &self,
+        _store: &BS,
+        input: ActorMigrationInput,
+    ) -> anyhow::Result<ActorMigrationOutput> {
+        Ok(ActorMigrationOutput { new_code_cid: self.new_code_cid, new_head: input.head })
+    }
+}
+
+/// A concurrent map from keys describing some old state to the CID of its migrated form.
+/// Many actors share identical sub-structures (e.g. empty HAMTs and AMTs, or the same
+/// deadline partitions), which need only be migrated once.
+/// Keys should identify both the kind of structure and its old root, e.g. "partitions-<cid>".
+#[derive(Clone, Debug, Default)]
+pub struct MigrationCache {
+    cache: Arc<RwLock<HashMap<String, Cid>>>,
+}
+
+impl MigrationCache {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn get(&self, key: &str) -> Option<Cid> {
+        self.cache.read().expect("migration cache poisoned").get(key).copied()
+    }
+
+    pub fn insert(&self, key: String, value: Cid) {
+        self.cache.write().expect("migration cache poisoned").insert(key, value);
+    }
+
+    /// Returns the cached value for a key, or computes, caches and returns it.
+    /// The value may be computed more than once if requested concurrently,
+    /// so the computation must be deterministic.
+    pub fn get_or_insert_with<F>(&self, key: String, f: F) -> anyhow::Result<Cid>
+    where
+        F: FnOnce() -> anyhow::Result<Cid>,
+    {
+        if let Some(cid) = self.get(&key) {
+            return Ok(cid);
+        }
+        let cid = f()?;
+        self.insert(key, cid);
+        Ok(cid)
+    }
+}
+
+/// Migrates a state tree from one set of actor code to another.
+/// By default every actor type in the old manifest is migrated to the code of the same type in
+/// the new manifest with its state unchanged. Actor types with a changed state schema must have a
+/// migrator registered with `add_migrator`.
+pub struct StateMigration<BS: Blockstore> {
+    migrations: HashMap<Cid, Box<dyn ActorMigration<BS>>>,
+    new_manifest: BiBTreeMap<Cid, Type>,
+    workers: usize,
+}
+
+impl<BS: Blockstore + Sync> StateMigration<BS> {
+    pub fn new(old_manifest: &BiBTreeMap<Cid, Type>, new_manifest: &BiBTreeMap<Cid, Type>) -> Self {
+        let mut migrations = HashMap::<Cid, Box<dyn ActorMigration<BS>>>::new();
+        for (old_code, typ) in old_manifest.iter() {
+            if let Some(new_code) = new_manifest.get_by_right(typ) {
+                migrations.insert(*old_code, Box::new(CodeMigrator::new(*new_code)));
+            }
+        }
+        let workers = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        Self { migrations, new_manifest: new_manifest.clone(), workers }
+    }
+
+    /// Sets the number of threads over which actor migrations are spread.
+    pub fn with_workers(self, workers: usize) -> Self {
+        Self { workers: workers.max(1), ..self }
+    }
+
+    /// Registers the migrator for actors with some old code CID, replacing any default.
+    pub fn add_migrator(&mut self, old_code: Cid, migrator: Box<dyn ActorMigration<BS>>) {
+        self.migrations.insert(old_code, migrator);
+    }
+
+    /// Migrates the actors of a versioned state root, returning a new state root with the
+    /// given version. The state info is carried over unchanged.
+    pub fn migrate_state_root(
+        &self,
+        store: &BS,
+        prior_epoch: ChainEpoch,
+        state_root: &Cid,
+        new_version: u64,
+    ) -> anyhow::Result<Cid> {
+        let root: StateRoot = store
+            .get_cbor(state_root)?
+            .ok_or_else(|| anyhow!("state root {} not found", state_root))?;
+        let actors = self.migrate_state_tree(store, prior_epoch, &root.actors)?;
+        store
+            .put_cbor(
+                &StateRoot { version: new_version, actors, info: root.info },
+                Code::Blake2b256,
+            )
+            .context("failed to store state root")
+    }
+
+    /// Migrates every actor in the state tree rooted at `root`, returning the new root.
+    /// The root is that of the actors HAMT itself, not a versioned state root: see
+    /// `migrate_state_root` for those.
+    /// Fails if any actor has a code CID with no migrator, or migrates to a code CID
+    /// absent from the new manifest.
+    pub fn migrate_state_tree(
+        &self,
+        store: &BS,
+        prior_epoch: ChainEpoch,
+        root: &Cid,
+    ) -> anyhow::Result<Cid> {
+        let tree = Tree::load(store, root).context("failed to load state tree")?;
+        let mut actors = Vec::new();
+        tree.for_each(|address, actor| {
+            actors.push((*address, actor.clone()));
+            Ok(())
+        })?;
+
+        let cache = MigrationCache::new();
+        let chunk_size = (actors.len() + self.workers - 1) / self.workers;
+        let migrated = std::thread::scope(|scope| {
+            let handles: Vec<_> = actors
+                .chunks(chunk_size.max(1))
+                .map(|chunk| {
+                    let cache = &cache;
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|(address, actor)| {
+                                self.migrate_actor(store, prior_epoch, cache, address, actor)
+                                    .map(|actor| (*address, actor))
+                            })
+                            .collect::<anyhow::Result<Vec<_>>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().map_err(|_| anyhow!("actor migration worker panicked"))?)
+                .collect::<anyhow::Result<Vec<_>>>()
+        })?;
+
+        // Update the existing tree in place, so subtrees holding only unchanged actors keep their
+        // CIDs and needn't be rewritten.
+        let mut new_tree: Map<BS, Actor> =
+            Map::load(root, store).context("failed to load state tree")?;
+        for ((address, actor), (_, migrated)) in actors.iter().zip(migrated.into_iter().flatten()) {
+            if migrated == *actor {
+                continue;
+            }
+            new_tree
+                .set(address.to_bytes().into(), migrated)
+                .with_context(|| format!("failed to set actor {address}"))?;
+        }
+        new_tree.flush().context("failed to flush state tree")
+    }
+
+    /// Migrates the state tree and checks the invariants of the result.
+    /// The total balance of all actors is expected to be unchanged by the migration.
+    pub fn migrate_and_check(
+        &self,
+        store: &BS,
+        policy: &Policy,
+        prior_epoch: ChainEpoch,
+        root: &Cid,
+    ) -> anyhow::Result<(Cid, MessageAccumulator)> {
+        let mut total_balance = TokenAmount::zero();
+        Tree::load(store, root)?.for_each(|_, actor| {
+            total_balance += &actor.balance;
+            Ok(())
+        })?;
+
+        let new_root = self.migrate_state_tree(store, prior_epoch, root)?;
+        let acc = check_state_invariants(
+            &self.new_manifest,
+            policy,
+            Tree::load(store, &new_root)?,
+            &total_balance,
+            prior_epoch,
+        )?;
+        Ok((new_root, acc))
+    }
+
+    fn migrate_actor(
+        &self,
+        store: &BS,
+        prior_epoch: ChainEpoch,
+        cache: &MigrationCache,
+        address: &Address,
+        actor: &Actor,
+    ) -> anyhow::Result<Actor> {
+        let migrator = self
+            .migrations
+            .get(&actor.code)
+            .ok_or_else(|| anyhow!("no migrator for actor {} with code {}", address, actor.code))?;
+        let output = migrator
+            .migrate_state(
+                store,
+                ActorMigrationInput {
+                    address: *address,
+                    balance: actor.balance.clone(),
+                    head: actor.head,
+                    prior_epoch,
+                    cache: cache.clone(),
+                },
+            )
+            .with_context(|| format!("failed to migrate actor {address}"))?;
+        if self.new_manifest.get_by_left(&output.new_code_cid).is_none() {
+            return Err(anyhow!(
+                "actor {} migrated to code {} not in new manifest",
+                address,
+                output.new_code_cid
+            ));
+        }
+        Ok(Actor { code: output.new_code_cid, head: output.new_head, ..actor.clone() })
+    }
+}
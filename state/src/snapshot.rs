@@ -0,0 +1,207 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
+
+use anyhow::{anyhow, Context};
+use bimap::BiBTreeMap;
+use cid::Cid;
+use fil_actor_system::State as SystemState;
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::SYSTEM_ACTOR_ADDR;
+use fvm_ipld_blockstore::{Blockstore, MemoryBlockstore};
+use fvm_ipld_car::CarHeader;
+use fvm_ipld_encoding::tuple::*;
+use fvm_ipld_encoding::{from_slice, CborStore};
+use fvm_shared::clock::ChainEpoch;
+use num_traits::FromPrimitive;
+use serde::de::IgnoredAny;
+
+use crate::check::{StateRoot, Tree};
+
+/// The parts of a block header needed to locate the state it was built on.
+/// All other fields are skipped without being decoded.
+#[derive(Deserialize_tuple)]
+struct BlockHeader {
+    _miner: IgnoredAny,
+    _ticket: IgnoredAny,
+    _election_proof: IgnoredAny,
+    _beacon_entries: IgnoredAny,
+    _win_post_proof: IgnoredAny,
+    _parents: IgnoredAny,
+    _parent_weight: IgnoredAny,
+    height: ChainEpoch,
+    parent_state_root: Cid,
+    _parent_message_receipts: IgnoredAny,
+    _messages: IgnoredAny,
+    _bls_aggregate: IgnoredAny,
+    _timestamp: IgnoredAny,
+    _block_sig: IgnoredAny,
+    _fork_signaling: IgnoredAny,
+    _parent_base_fee: IgnoredAny,
+}
+
+/// Loads the blocks of a CARv1 chain snapshot into a store, returning the CAR's roots.
+/// For a chain snapshot the roots are the block headers of the tipset at its head.
+pub fn load_car<BS: Blockstore, R: Read + Send + Unpin>(
+    store: &BS,
+    reader: R,
+) -> anyhow::Result<Vec<Cid>> {
+    let reader = futures::io::AllowStdIo::new(reader);
+    futures::executor::block_on(fvm_ipld_car::load_car_unchecked(store, reader))
+        .context("failed to load snapshot CAR")
+}
+
+/// A blockstore reading blocks on demand from a CARv1 file, such as a chain snapshot.
+/// Opening the store scans the file once to index the offset of each block, so only the
+/// index (rather than every block) is held in memory.
+/// Blocks put into the store are kept in memory, and never written to the file.
+pub struct CarBlockstore<R> {
+    reader: RefCell<R>,
+    roots: Vec<Cid>,
+    index: HashMap<Cid, (u64, usize)>,
+    written: MemoryBlockstore,
+}
+
+impl<R: Read + Seek> CarBlockstore<R> {
+    /// Indexes the blocks of a CARv1 file.
+    pub fn new(mut reader: R) -> anyhow::Result<Self> {
+        let header_len = read_varint(&mut reader)?.ok_or_else(|| anyhow!("CAR has no header"))?;
+        let mut buf = vec![0; header_len as usize];
+        reader.read_exact(&mut buf).context("failed to read CAR header")?;
+        let header: CarHeader = from_slice(&buf).context("failed to decode CAR header")?;
+        if header.version != 1 {
+            return Err(anyhow!("unsupported CAR version {}", header.version));
+        }
+
+        let mut index = HashMap::new();
+        let mut offset = reader.stream_position()?;
+        while let Some(section_len) = read_varint(&mut reader)? {
+            let section_start = reader.stream_position()?;
+            buf.resize(section_len as usize, 0);
+            reader
+                .read_exact(&mut buf)
+                .with_context(|| format!("truncated CAR section at offset {}", offset))?;
+            let mut data = &buf[..];
+            let cid = Cid::read_bytes(&mut data)
+                .with_context(|| format!("invalid CID in CAR section at offset {}", offset))?;
+            let cid_len = buf.len() - data.len();
+            index.insert(cid, (section_start + cid_len as u64, section_len as usize - cid_len));
+            offset = section_start + section_len;
+        }
+        Ok(Self {
+            reader: RefCell::new(reader),
+            roots: header.roots,
+            index,
+            written: MemoryBlockstore::new(),
+        })
+    }
+
+    /// The CAR's roots. For a chain snapshot these are the block headers of the tipset at
+    /// its head.
+    pub fn roots(&self) -> &[Cid] {
+        &self.roots
+    }
+}
+
+impl<R: Read + Seek> Blockstore for CarBlockstore<R> {
+    fn get(&self, k: &Cid) -> anyhow::Result<Option<Vec<u8>>> {
+        if let Some(block) = self.written.get(k)? {
+            return Ok(Some(block));
+        }
+        let (offset, len) = match self.index.get(k) {
+            Some(entry) => *entry,
+            None => return Ok(None),
+        };
+        let mut reader = self.reader.borrow_mut();
+        let mut block = vec![0; len];
+        reader.seek(SeekFrom::Start(offset))?;
+        reader.read_exact(&mut block).with_context(|| format!("failed to read block {}", k))?;
+        Ok(Some(block))
+    }
+
+    fn has(&self, k: &Cid) -> anyhow::Result<bool> {
+        Ok(self.index.contains_key(k) || self.written.has(k)?)
+    }
+
+    fn put_keyed(&self, k: &Cid, block: &[u8]) -> anyhow::Result<()> {
+        self.written.put_keyed(k, block)
+    }
+}
+
+// Reads an unsigned LEB128 varint, returning None at the end of the input.
+fn read_varint<R: Read>(reader: &mut R) -> anyhow::Result<Option<u64>> {
+    let mut value = 0u64;
+    for i in 0..10 {
+        let mut byte = [0u8];
+        if let Err(e) = reader.read_exact(&mut byte) {
+            return if i == 0 && e.kind() == ErrorKind::UnexpectedEof {
+                Ok(None)
+            } else {
+                Err(anyhow!(e).context("truncated varint"))
+            };
+        }
+        value |= ((byte[0] & 0x7f) as u64) << (7 * i);
+        if byte[0] & 0x80 == 0 {
+            return Ok(Some(value));
+        }
+    }
+    Err(anyhow!("varint too long"))
+}
+
+/// Finds the state tree a tipset was executed on, given the CID of one of its block headers.
+/// Returns the root of the actors HAMT and the epoch at which that state was computed.
+pub fn state_for_tipset<BS: Blockstore>(
+    store: &BS,
+    header: &Cid,
+) -> anyhow::Result<(Cid, ChainEpoch)> {
+    let header: BlockHeader =
+        store.get_cbor(header)?.ok_or_else(|| anyhow!("block header {} not found", header))?;
+    Ok((actors_root(store, &header.parent_state_root)?, header.height - 1))
+}
+
+/// Returns the root of the actors HAMT for a state root.
+/// State roots since actors v3 are versioned, with the HAMT root inside. Earlier state roots
+/// are the HAMT itself.
+pub fn actors_root<BS: Blockstore>(store: &BS, state_root: &Cid) -> anyhow::Result<Cid> {
+    let block =
+        store.get(state_root)?.ok_or_else(|| anyhow!("state root {} not found", state_root))?;
+    // Tell the two apart by shape: a versioned root has three fields, a HAMT node two.
+    let fields: Vec<IgnoredAny> = from_slice(&block)
+        .with_context(|| format!("state root {} is not a CBOR list", state_root))?;
+    match fields.len() {
+        3 => {
+            let root: StateRoot = from_slice(&block)
+                .with_context(|| format!("failed to decode state root {}", state_root))?;
+            Ok(root.actors)
+        }
+        2 => Ok(*state_root),
+        n => Err(anyhow!(
+            "state root {} has {} fields, expected a versioned root or HAMT",
+            state_root,
+            n
+        )),
+    }
+}
+
+/// Loads the manifest of builtin actor code CIDs from the system actor's state.
+pub fn load_manifest<BS: Blockstore>(tree: &Tree<'_, BS>) -> anyhow::Result<BiBTreeMap<Cid, Type>> {
+    let system = tree
+        .map
+        .get(&SYSTEM_ACTOR_ADDR.to_bytes())?
+        .ok_or_else(|| anyhow!("system actor not found"))?;
+    let state: SystemState = tree
+        .store
+        .get_cbor(&system.head)?
+        .ok_or_else(|| anyhow!("system actor state {} not found", system.head))?;
+    let entries = state.get_builtin_actors(tree.store).map_err(|e| anyhow!(e))?;
+
+    let mut manifest = BiBTreeMap::new();
+    for (name, code) in entries {
+        let typ = (1..)
+            .map_while(Type::from_i32)
+            .find(|t| t.name() == name)
+            .ok_or_else(|| anyhow!("unknown actor {} in manifest", name))?;
+        manifest.insert(code, typ);
+    }
+    Ok(manifest)
+}
@@ -0,0 +1,169 @@
+use std::cell::RefCell;
+use std::io::Cursor;
+
+use cid::multihash::Multihash;
+use cid::Cid;
+use fil_actor_system::State as SystemState;
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::{Map, SYSTEM_ACTOR_ADDR};
+use fil_builtin_actors_state::check::{Actor, StateRoot, Tree};
+use fil_builtin_actors_state::snapshot::{
+    actors_root, load_car, load_manifest, state_for_tipset, CarBlockstore,
+};
+use fvm_ipld_blockstore::{Blockstore, MemoryBlockstore};
+use fvm_ipld_car::CarHeader;
+use fvm_ipld_encoding::CborStore;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use multihash::Code;
+
+// A store that remembers every block put, in order, so they can be written to a CAR.
+#[derive(Default)]
+struct RecordingBlockstore {
+    store: MemoryBlockstore,
+    blocks: RefCell<Vec<(Cid, Vec<u8>)>>,
+}
+
+impl Blockstore for RecordingBlockstore {
+    fn get(&self, k: &Cid) -> anyhow::Result<Option<Vec<u8>>> {
+        self.store.get(k)
+    }
+
+    fn put_keyed(&self, k: &Cid, block: &[u8]) -> anyhow::Result<()> {
+        self.blocks.borrow_mut().push((*k, block.to_vec()));
+        self.store.put_keyed(k, block)
+    }
+}
+
+const HEIGHT: ChainEpoch = 100;
+
+struct Fixture {
+    car: Vec<u8>,
+    blocks: Vec<(Cid, Vec<u8>)>,
+    header: Cid,
+    state_root: Cid,
+    actors: Cid,
+    system_code: Cid,
+}
+
+// Builds a snapshot CAR of a single tipset, executed on a state tree holding only the system actor.
+fn snapshot() -> Fixture {
+    let store = RecordingBlockstore::default();
+    let system_code = Cid::new_v1(0x55, Multihash::wrap(0, b"fil/test/system").unwrap());
+    let builtin_actors = store
+        .put_cbor(&vec![(Type::System.name().to_string(), system_code)], Code::Blake2b256)
+        .unwrap();
+    let system_head = store.put_cbor(&SystemState { builtin_actors }, Code::Blake2b256).unwrap();
+
+    let mut tree = Map::new(&store);
+    tree.set(
+        SYSTEM_ACTOR_ADDR.to_bytes().into(),
+        Actor {
+            code: system_code,
+            head: system_head,
+            call_seq_num: 0,
+            balance: TokenAmount::default(),
+            address: None,
+        },
+    )
+    .unwrap();
+    let actors = tree.flush().unwrap();
+    let info = store.put_cbor(&(), Code::Blake2b256).unwrap();
+    let state_root =
+        store.put_cbor(&StateRoot { version: 5, actors, info }, Code::Blake2b256).unwrap();
+
+    // Only the height and parent state root of the header are decoded.
+    let header = store
+        .put_cbor(&(0, 0, 0, 0, 0, 0, 0, HEIGHT, state_root, 0, 0, 0, 0, 0, 0, 0), Code::Blake2b256)
+        .unwrap();
+
+    let blocks = store.blocks.take();
+    let mut car = futures::io::Cursor::new(Vec::new());
+    let mut stream = futures::stream::iter(blocks.clone());
+    futures::executor::block_on(
+        CarHeader::from(vec![header]).write_stream_async(&mut car, &mut stream),
+    )
+    .unwrap();
+    Fixture { car: car.into_inner(), blocks, header, state_root, actors, system_code }
+}
+
+#[test]
+fn load_car_loads_all_blocks() {
+    let fixture = snapshot();
+    let store = MemoryBlockstore::new();
+    let roots = load_car(&store, Cursor::new(&fixture.car)).unwrap();
+    assert_eq!(vec![fixture.header], roots);
+    for (cid, block) in &fixture.blocks {
+        assert_eq!(Some(block), store.get(cid).unwrap().as_ref());
+    }
+}
+
+#[test]
+fn car_blockstore_reads_indexed_blocks() {
+    let fixture = snapshot();
+    let store = CarBlockstore::new(Cursor::new(&fixture.car)).unwrap();
+    assert_eq!(&[fixture.header], store.roots());
+    // Read in reverse to exercise seeking back through the file.
+    for (cid, block) in fixture.blocks.iter().rev() {
+        assert!(store.has(cid).unwrap());
+        assert_eq!(Some(block), store.get(cid).unwrap().as_ref());
+    }
+
+    // Blocks not in the CAR are absent until put.
+    let cid = Cid::new_v1(0x55, Multihash::wrap(0, b"absent").unwrap());
+    assert_eq!(None, store.get(&cid).unwrap());
+    store.put_keyed(&cid, b"absent").unwrap();
+    assert_eq!(Some(b"absent".to_vec()), store.get(&cid).unwrap());
+}
+
+#[test]
+fn car_blockstore_rejects_truncated_car() {
+    let fixture = snapshot();
+    let truncated = &fixture.car[..fixture.car.len() - 1];
+    let err = CarBlockstore::new(Cursor::new(truncated)).err().unwrap();
+    assert!(err.to_string().contains("truncated CAR section"), "{}", err);
+}
+
+#[test]
+fn state_for_tipset_finds_parent_state() {
+    let fixture = snapshot();
+    let store = CarBlockstore::new(Cursor::new(&fixture.car)).unwrap();
+    let (actors, epoch) = state_for_tipset(&store, &fixture.header).unwrap();
+    assert_eq!(fixture.actors, actors);
+    assert_eq!(HEIGHT - 1, epoch);
+
+    let manifest = load_manifest(&Tree::load(&store, &actors).unwrap()).unwrap();
+    assert_eq!(Some(&Type::System), manifest.get_by_left(&fixture.system_code));
+    assert_eq!(1, manifest.len());
+}
+
+#[test]
+fn actors_root_of_versioned_and_bare_roots() {
+    let fixture = snapshot();
+    let store = CarBlockstore::new(Cursor::new(&fixture.car)).unwrap();
+    assert_eq!(fixture.actors, actors_root(&store, &fixture.state_root).unwrap());
+    // A state root from before versioning is the HAMT itself.
+    assert_eq!(fixture.actors, actors_root(&store, &fixture.actors).unwrap());
+}
+
+#[test]
+fn actors_root_rejects_malformed_roots() {
+    let store = MemoryBlockstore::new();
+
+    // Three fields, but not those of a versioned root.
+    let bad_fields = store.put_cbor(&(5u64, 6u64, 7u64), Code::Blake2b256).unwrap();
+    let err = actors_root(&store, &bad_fields).unwrap_err();
+    assert!(err.to_string().contains("failed to decode state root"), "{}", err);
+
+    let not_a_list = store.put_cbor(&5u64, Code::Blake2b256).unwrap();
+    let err = actors_root(&store, &not_a_list).unwrap_err();
+    assert!(err.to_string().contains("is not a CBOR list"), "{}", err);
+
+    let wrong_length = store.put_cbor(&(5u64,), Code::Blake2b256).unwrap();
+    let err = actors_root(&store, &wrong_length).unwrap_err();
+    assert!(err.to_string().contains("has 1 fields"), "{}", err);
+
+    let missing = Cid::new_v1(0x71, Multihash::wrap(0, b"missing").unwrap());
+    let err = actors_root(&store, &missing).unwrap_err();
+    assert!(err.to_string().contains("not found"), "{}", err);
+}
@@ -1,3 +1,4 @@
+use cid::Cid;
 use frc46_token::receiver::{FRC46TokenReceived, FRC46_TOKEN_TYPE};
 use frc46_token::token::types::BurnParams;
 use fvm_actor_utils::receiver::UniversalReceiverParams;
@@ -7,6 +8,7 @@ use fvm_shared::address::Address;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::deal::DealID;
 use fvm_shared::econ::TokenAmount;
+use fvm_shared::sector::SectorNumber;
 use fvm_shared::{ActorID, METHOD_SEND};
 use num_traits::Zero;
 
@@ -20,11 +22,11 @@ use fil_actor_miner::{IsControllingAddressParam, PowerPair};
 use fil_actor_power::{UpdateClaimedPowerParams, UpdatePledgeTotalParams};
 use fil_actor_verifreg::GetClaimsParams;
 use fil_actors_runtime::{
-    BURNT_FUNDS_ACTOR_ADDR, REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR,
-    VERIFIED_REGISTRY_ACTOR_ADDR,
+    EventBuilder, BURNT_FUNDS_ACTOR_ADDR, REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR,
+    STORAGE_POWER_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
 };
 
-use crate::trace::ExpectInvocation;
+use crate::trace::{EmittedEvent, ExpectInvocation};
 
 /// Static helper functions for creating invocation expectations.
 pub struct Expect {}
@@ -286,4 +288,43 @@ impl Expect {
             ..Default::default()
         }
     }
+
+    /// Builds a market deal lifecycle event as emitted by the storage market actor.
+    pub fn build_market_event(
+        typ: &str,
+        deal_id: DealID,
+        client: ActorID,
+        provider: ActorID,
+    ) -> EmittedEvent {
+        EmittedEvent {
+            emitter: STORAGE_MARKET_ACTOR_ADDR.id().unwrap(),
+            event: EventBuilder::new()
+                .typ(typ)
+                .field_indexed("id", &deal_id)
+                .field_indexed("client", &client)
+                .field_indexed("provider", &provider)
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// Builds a sector lifecycle event carrying the sector's data, as emitted by a miner actor.
+    pub fn build_sector_event(
+        typ: &str,
+        miner: ActorID,
+        sector: SectorNumber,
+        unsealed_cid: Option<Cid>,
+        deals: &[DealID],
+    ) -> EmittedEvent {
+        EmittedEvent {
+            emitter: miner,
+            event: EventBuilder::new()
+                .typ(typ)
+                .field_indexed("sector", &sector)
+                .field_indexed("unsealed-cid", &unsealed_cid)
+                .field("deals", deals)
+                .build()
+                .unwrap(),
+        }
+    }
 }
@@ -71,7 +71,7 @@ use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
 use std::ops::Add;
-use trace::InvocationTrace;
+use trace::{EmittedEvent, InvocationTrace};
 
 use crate::util::get_state;
 use crate::util::serialize_ok;
@@ -236,6 +236,7 @@ where
             read_only: false,
             policy: &Policy::default(),
             subinvocations: RefCell::new(vec![]),
+            events: RefCell::new(vec![]),
         };
         let res = new_ctx.invoke();
 
@@ -648,6 +649,7 @@ where
     read_only: bool,
     policy: &'invocation Policy,
     subinvocations: RefCell<Vec<InvocationTrace>>,
+    events: RefCell<Vec<EmittedEvent>>,
 }
 
 impl<'invocation, 'bs, BS> InvocationCtx<'invocation, 'bs, BS>
@@ -712,6 +714,7 @@ where
                 read_only: false,
                 policy: self.policy,
                 subinvocations: RefCell::new(vec![]),
+                events: RefCell::new(vec![]),
             };
             if is_account {
                 new_ctx.create_actor(*ACCOUNT_ACTOR_CODE_ID, target_id, None).unwrap();
@@ -737,6 +740,8 @@ where
             Ok(rb) => (rb, ExitCode::OK),
             Err(ae) => (None, ae.exit_code()),
         };
+        // Events emitted by a failed invocation are discarded along with its state changes.
+        let events = if code.is_success() { self.events.take() } else { vec![] };
         let mut msg = self.msg.clone();
         msg.to = match self.resolve_target(&self.msg.to) {
             Ok((_, addr)) => addr, // use normalized address in trace
@@ -751,6 +756,7 @@ where
             code,
             ret,
             subinvocations: self.subinvocations.take(),
+            events,
         }
     }
 
@@ -1045,6 +1051,7 @@ where
             read_only: send_flags.read_only(),
             policy: self.policy,
             subinvocations: RefCell::new(vec![]),
+            events: RefCell::new(vec![]),
         };
         let res = new_ctx.invoke();
         let invoc = new_ctx.gather_trace(res.clone());
@@ -1169,9 +1176,14 @@ where
         Ok(Cid::new_v1(IPLD_RAW, Multihash::wrap(0, b"faketipset").unwrap()))
     }
 
-    // TODO No support for events yet.
-    fn emit_event(&self, _event: &ActorEvent) -> Result<(), ActorError> {
-        unimplemented!()
+    fn emit_event(&self, event: &ActorEvent) -> Result<(), ActorError> {
+        if self.read_only() {
+            return Err(ActorError::read_only("cannot emit event in read-only mode".into()));
+        }
+        self.events
+            .borrow_mut()
+            .push(EmittedEvent { emitter: self.to().id().unwrap(), event: event.clone() });
+        Ok(())
     }
 
     fn read_only(&self) -> bool {
@@ -2,7 +2,15 @@ use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::address::Address;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
-use fvm_shared::MethodNum;
+use fvm_shared::event::ActorEvent;
+use fvm_shared::{ActorID, MethodNum};
+
+/// An event emitted by an actor during an invocation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmittedEvent {
+    pub emitter: ActorID,
+    pub event: ActorEvent,
+}
 
 /// A trace of an actor method invocation.
 #[derive(Clone, Debug)]
@@ -15,6 +23,8 @@ pub struct InvocationTrace {
     pub code: ExitCode,
     pub ret: Option<IpldBlock>,
     pub subinvocations: Vec<InvocationTrace>,
+    /// Events emitted directly by the invoked actor. Empty if the invocation failed.
+    pub events: Vec<EmittedEvent>,
 }
 
 /// An expectation for a method invocation trace.
@@ -37,6 +47,7 @@ pub struct ExpectInvocation {
     pub code: ExitCode,
     pub ret: Option<Option<IpldBlock>>,
     pub subinvocs: Option<Vec<ExpectInvocation>>,
+    pub events: Option<Vec<EmittedEvent>>,
 }
 
 impl ExpectInvocation {
@@ -70,6 +81,13 @@ impl ExpectInvocation {
                 id, r, invoc.ret
             );
         }
+        if let Some(events) = &self.events {
+            assert_eq!(
+                events, &invoc.events,
+                "{} unexpected events: expected: {:?}, was: {:?}",
+                id, events, invoc.events
+            );
+        }
         if let Some(expect_subinvocs) = &self.subinvocs {
             let subinvocs = &invoc.subinvocations;
 
@@ -138,6 +156,7 @@ impl Default for ExpectInvocation {
             code: ExitCode::OK,
             ret: None,
             subinvocs: None,
+            events: None,
         }
     }
 }
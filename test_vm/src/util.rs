@@ -936,6 +936,7 @@ pub fn verifreg_add_verifier<BS: Blockstore>(
         value: TokenAmount::zero(),
         method: VerifregMethod::AddVerifier as u64,
         params: serialize(&add_verifier_params, "verifreg add verifier params").unwrap(),
+        expiration: None,
     };
 
     apply_ok(
@@ -1329,6 +1330,12 @@ pub fn market_publish_deal<BS: Blockstore>(
         to: STORAGE_MARKET_ACTOR_ADDR,
         method: MarketMethod::PublishStorageDeals as u64,
         subinvocs: Some(expect_publish_invocs),
+        events: Some(vec![Expect::build_market_event(
+            "deal-published",
+            ret.ids[0],
+            v.resolve_id_address(deal_client).unwrap().id().unwrap(),
+            miner_id.id().unwrap(),
+        )]),
         ..Default::default()
     }
     .matches(v.take_invocations().last().unwrap());
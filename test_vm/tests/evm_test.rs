@@ -3,19 +3,22 @@ use std::sync::Arc;
 use ethers::prelude::abigen;
 use ethers::providers::Provider;
 use ethers::{core::types::Address as EthAddress, prelude::builders::ContractCall};
+use fil_actor_evm::{BytecodeHash, State as EvmState, TopLevelMessage};
+use fil_actor_init::State as InitState;
 use fil_actors_evm_shared::uints::U256;
 use fil_actors_runtime::{
-    test_utils::ETHACCOUNT_ACTOR_CODE_ID, test_utils::EVM_ACTOR_CODE_ID, EAM_ACTOR_ADDR,
-    EAM_ACTOR_ID,
+    make_map_with_root_and_bitwidth, test_utils::ETHACCOUNT_ACTOR_CODE_ID,
+    test_utils::EVM_ACTOR_CODE_ID, EAM_ACTOR_ADDR, EAM_ACTOR_ID, INIT_ACTOR_ADDR,
 };
 use fvm_ipld_blockstore::{Blockstore, MemoryBlockstore};
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::{strict_bytes, BytesDe, RawBytes};
 use fvm_shared::{address::Address, econ::TokenAmount};
-use fvm_shared::{ActorID, METHOD_SEND};
+use fvm_shared::{ActorID, HAMT_BIT_WIDTH, METHOD_SEND};
 use num_traits::Zero;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use test_vm::util::serialize_ok;
+use test_vm::util::{assert_invariants, expect_invariants, get_state, serialize_ok};
 use test_vm::VM;
 use test_vm::{
     util::{apply_ok, create_accounts},
@@ -94,6 +97,24 @@ fn evm_call_test<BS: Blockstore>(v: &dyn VM<BS>) {
     assert_eq!(0, evm_ret, "expected contract to return 0 on success");
 }
 
+#[test]
+fn evm_call_traced() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::<MemoryBlockstore>::new_with_singletons(&store);
+
+    fil_actor_evm::interpreter::tracer::start();
+    evm_call_test(&v);
+    let trace = fil_actor_evm::interpreter::tracer::finish();
+
+    let first = trace.struct_logs.first().expect("expected a non-empty trace");
+    assert_eq!((first.pc, first.depth), (0, 1));
+    let last = trace.struct_logs.iter().rev().find(|s| s.depth == 1).unwrap();
+    assert_eq!(last.op, "RETURN");
+
+    let json = serde_json::to_value(&trace).unwrap();
+    assert_eq!(json["structLogs"].as_array().unwrap().len(), trace.struct_logs.len());
+}
+
 #[test]
 fn evm_create() {
     let store = MemoryBlockstore::new();
@@ -779,3 +800,107 @@ fn evm_init_revert_data_test<BS: Blockstore>(v: &dyn VM<BS>) {
     expected[31] = 0x42;
     assert_eq!(revert_data, expected);
 }
+
+// Creates a contract, returning the creating account and the contract's ID and delegated addresses.
+fn create_contract<BS: Blockstore>(v: &dyn VM<BS>) -> (Address, Address, Address) {
+    let account = create_accounts(v, 1, &TokenAmount::from_whole(10_000))[0];
+    let bytecode =
+        hex::decode(include_str!("../../actors/evm/tests/contracts/Recursive.hex")).unwrap();
+    let create_return: fil_actor_eam::CreateExternalReturn = apply_ok(
+        v,
+        &account,
+        &EAM_ACTOR_ADDR,
+        &TokenAmount::zero(),
+        fil_actor_eam::Method::CreateExternal as u64,
+        Some(fil_actor_eam::CreateExternalParams(bytecode)),
+    )
+    .deserialize()
+    .unwrap();
+    let delegated = Address::new_delegated(EAM_ACTOR_ID, &create_return.eth_address.0).unwrap();
+    (account, Address::new_id(create_return.actor_id), delegated)
+}
+
+// Replaces an actor's state, and commits it to the state tree.
+fn set_state<BS: Blockstore, S: Serialize>(v: &TestVM<BS>, addr: &Address, state: &S) {
+    let mut actor = v.get_actor(addr).unwrap();
+    actor.head = v.put_store(state);
+    v.set_actor(addr, actor);
+    v.checkpoint();
+}
+
+#[test]
+fn evm_invariants_bytecode_hash() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::<MemoryBlockstore>::new_with_singletons(&store);
+    let (_, contract, _) = create_contract(&v);
+    assert_invariants(&v);
+
+    let state: EvmState = get_state(&v, &contract).unwrap();
+    set_state(&v, &contract, &EvmState { bytecode_hash: BytecodeHash::ZERO, ..state });
+    expect_invariants(
+        &v,
+        &[Regex::new("^bytecode hash 0{64} does not match keccak256 of bytecode").unwrap()],
+    );
+}
+
+#[test]
+fn evm_invariants_tombstone_nonce() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::<MemoryBlockstore>::new_with_singletons(&store);
+    let (account, contract, _) = create_contract(&v);
+    let origin = account.id().unwrap();
+
+    // A tombstone from a message the origin has already sent is valid.
+    let state: EvmState = get_state(&v, &contract).unwrap();
+    set_state(
+        &v,
+        &contract,
+        &EvmState { tombstone: Some(TopLevelMessage { origin, nonce: 0 }), ..state },
+    );
+    assert_invariants(&v);
+
+    // The origin can't have self-destructed the contract with a message it hasn't yet sent.
+    let state: EvmState = get_state(&v, &contract).unwrap();
+    set_state(
+        &v,
+        &contract,
+        &EvmState { tombstone: Some(TopLevelMessage { origin, nonce: 5 }), ..state },
+    );
+    expect_invariants(
+        &v,
+        &[Regex::new(&format!(
+            "^{contract} tombstone nonce 5 is not less than origin {origin} nonce 1$"
+        ))
+        .unwrap()],
+    );
+}
+
+#[test]
+fn evm_invariants_delegated_address_mapping() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::<MemoryBlockstore>::new_with_singletons(&store);
+    let (account, contract, delegated) = create_contract(&v);
+
+    // Point the contract's delegated address at the account instead.
+    let mut state: InitState = get_state(&v, &INIT_ACTOR_ADDR).unwrap();
+    let mut address_map =
+        make_map_with_root_and_bitwidth(&state.address_map, &store, HAMT_BIT_WIDTH).unwrap();
+    address_map.set(delegated.to_bytes().into(), account.id().unwrap()).unwrap();
+    state.address_map = address_map.flush().unwrap();
+    set_state(&v, &INIT_ACTOR_ADDR, &state);
+
+    expect_invariants(
+        &v,
+        &[
+            Regex::new(&format!(
+                "^{contract} delegated address {delegated} maps to ID {} in init actor$",
+                account.id().unwrap()
+            ))
+            .unwrap(),
+            Regex::new(&format!(
+                "^init actor maps {delegated} to {account}, which has a different address$"
+            ))
+            .unwrap(),
+        ],
+    );
+}
@@ -0,0 +1,135 @@
+use fil_actor_evm::Method as EvmMethod;
+use fil_actors_evm_shared::address::EthAddress;
+use fil_actors_evm_shared::uints::U256;
+use fil_actors_runtime::EAM_ACTOR_ADDR;
+use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_ipld_encoding::{strict_bytes, BytesDe};
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
+use num_traits::Zero;
+use serde::{Deserialize, Serialize};
+use test_vm::util::{apply_ok, create_accounts};
+use test_vm::{TestVM, VM};
+
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+struct ContractParams(#[serde(with = "strict_bytes")] pub Vec<u8>);
+
+// A contract exercising EIP-1153 transient storage slot 0, dispatching on the first byte of
+// the call data. The etk assembler doesn't know TLOAD and TSTORE, so it's assembled by hand.
+//
+//   0x01 store-reenter:     TSTORE(0, 0x42); return the result of calling itself with 0x02.
+//   0x02 load:              return TLOAD(0).
+//   0x03 store:             TSTORE(0, 0x42).
+//   0x04 store-revert:      TSTORE(0, 0x43); REVERT.
+//   0x05 store-call-revert: TSTORE(0, 0x42); call itself with 0x04; return TLOAD(0).
+//   0x06 delegate-store:    DELEGATECALL the address in the following 20 bytes with 0x03;
+//                           return TLOAD(0).
+const TRANSIENT_STORAGE_INITCODE: &str = concat!(
+    // Constructor: copy the 183 byte body to memory and return it.
+    "60b780600c6000396000f300",
+    // Dispatch.
+    "60003560f81c806001146034578060021460525780600314605e578060041460655780600514606f5780600614",
+    "609357600080fd",
+    // store-reenter
+    "5b604260005d600260005360206000600160006000305af15060206000f3",
+    // load
+    "5b60005c60005260206000f3",
+    // store
+    "5b604260005d00",
+    // store-revert
+    "5b604360005d600080fd",
+    // store-call-revert
+    "5b604260005d600460005360006000600160006000305af15060005c60005260206000f3",
+    // delegate-store
+    "5b60013560601c60036000536000600060016000845af4505060005c60005260206000f3",
+);
+
+// Creates a transient storage contract, returning its Ethereum address.
+fn create_contract(v: &dyn VM<MemoryBlockstore>, from: &Address) -> EthAddress {
+    let initcode = hex::decode(TRANSIENT_STORAGE_INITCODE).unwrap();
+    let ret: fil_actor_eam::CreateExternalReturn = apply_ok(
+        v,
+        from,
+        &EAM_ACTOR_ADDR,
+        &TokenAmount::zero(),
+        fil_actor_eam::Method::CreateExternal as u64,
+        Some(fil_actor_eam::CreateExternalParams(initcode)),
+    )
+    .deserialize()
+    .unwrap();
+    ret.eth_address
+}
+
+// Invokes the contract in a new top-level message, returning the word it returns (if any).
+fn invoke(
+    v: &dyn VM<MemoryBlockstore>,
+    from: &Address,
+    contract: &Address,
+    input: Vec<u8>,
+) -> U256 {
+    let BytesDe(ret) = apply_ok(
+        v,
+        from,
+        contract,
+        &TokenAmount::zero(),
+        EvmMethod::InvokeContract as u64,
+        Some(ContractParams(input)),
+    )
+    .deserialize()
+    .unwrap();
+    if ret.is_empty() {
+        U256::ZERO
+    } else {
+        U256::from_big_endian(&ret)
+    }
+}
+
+fn setup(store: &MemoryBlockstore) -> (TestVM<'_, MemoryBlockstore>, Address, Address) {
+    let v = TestVM::<MemoryBlockstore>::new_with_singletons(store);
+    let account = create_accounts(&v, 1, &TokenAmount::from_whole(10_000))[0];
+    let contract = create_contract(&v, &account).into();
+    (v, account, contract)
+}
+
+#[test]
+fn transient_storage_visible_to_reentrant_call() {
+    let store = MemoryBlockstore::new();
+    let (v, account, contract) = setup(&store);
+    assert_eq!(U256::from(0x42), invoke(&v, &account, &contract, vec![0x01]));
+}
+
+#[test]
+fn transient_storage_cleared_for_next_message() {
+    let store = MemoryBlockstore::new();
+    let (v, account, contract) = setup(&store);
+    let other = create_accounts(&v, 1, &TokenAmount::from_whole(10_000))[0];
+
+    assert_eq!(U256::ZERO, invoke(&v, &account, &contract, vec![0x03]));
+    // A later message from the same origin, with the next nonce.
+    assert_eq!(U256::ZERO, invoke(&v, &account, &contract, vec![0x02]));
+
+    // And a message from a different origin.
+    assert_eq!(U256::ZERO, invoke(&v, &account, &contract, vec![0x03]));
+    assert_eq!(U256::ZERO, invoke(&v, &other, &contract, vec![0x02]));
+}
+
+#[test]
+fn transient_storage_shared_with_delegatecall() {
+    let store = MemoryBlockstore::new();
+    let (v, account, contract) = setup(&store);
+    let library = create_contract(&v, &account);
+
+    // The library's code writes to the calling contract's transient storage.
+    let mut input = vec![0x06];
+    input.extend_from_slice(&library.0);
+    assert_eq!(U256::from(0x42), invoke(&v, &account, &contract, input));
+}
+
+#[test]
+fn transient_storage_discarded_by_reverted_call() {
+    let store = MemoryBlockstore::new();
+    let (v, account, contract) = setup(&store);
+    // The reverted call's write of 0x43 doesn't survive.
+    assert_eq!(U256::from(0x42), invoke(&v, &account, &contract, vec![0x05]));
+}
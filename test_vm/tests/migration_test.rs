@@ -0,0 +1,353 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use bimap::BiBTreeMap;
+use cid::Cid;
+use fil_actor_account::State as AccountState;
+use fil_actor_init::ExecReturn;
+use fil_actor_multisig::{State as MultisigState, Transaction, TxnID};
+use fil_actors_runtime::cbor::serialize;
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::test_utils::{make_identity_cid, MULTISIG_ACTOR_CODE_ID};
+use fil_actors_runtime::{make_empty_map, make_map_with_root_and_bitwidth, INIT_ACTOR_ADDR};
+use fil_builtin_actors_state::check::{StateRoot, Tree};
+use fil_builtin_actors_state::migration::{
+    ActorMigration, ActorMigrationInput, ActorMigrationOutput, StateMigration,
+};
+use fvm_ipld_blockstore::{Blockstore, MemoryBlockstore};
+use fvm_ipld_encoding::tuple::*;
+use fvm_ipld_encoding::{CborStore, RawBytes};
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::{MethodNum, HAMT_BIT_WIDTH, METHOD_SEND};
+use multihash::Code;
+use num_traits::Zero;
+use test_vm::util::{apply_ok, create_accounts, get_state};
+use test_vm::{TestVM, VM};
+
+// Migrations run concurrently so need a thread-safe store.
+struct SyncBlockstore(Mutex<MemoryBlockstore>);
+
+impl Blockstore for SyncBlockstore {
+    fn get(&self, k: &Cid) -> anyhow::Result<Option<Vec<u8>>> {
+        self.0.lock().unwrap().get(k)
+    }
+
+    fn put_keyed(&self, k: &Cid, block: &[u8]) -> anyhow::Result<()> {
+        self.0.lock().unwrap().put_keyed(k, block)
+    }
+}
+
+// The test VM starts at epoch zero.
+const PRIOR_EPOCH: ChainEpoch = -1;
+
+// Returns a state tree with some accounts, and the manifest of actors it contains.
+fn setup() -> (SyncBlockstore, Cid, BiBTreeMap<Cid, Type>) {
+    setup_with_accounts(3)
+}
+
+fn setup_with_accounts(count: u64) -> (SyncBlockstore, Cid, BiBTreeMap<Cid, Type>) {
+    let store = MemoryBlockstore::new();
+    let (root, manifest) = {
+        let v = TestVM::<MemoryBlockstore>::new_with_singletons(&store);
+        create_accounts(&v, count, &TokenAmount::from_whole(10_000));
+        (v.checkpoint(), v.actor_manifest())
+    };
+    (SyncBlockstore(Mutex::new(store)), root, manifest)
+}
+
+// Returns a manifest with a new code CID for each actor type in the old manifest.
+fn new_code_manifest(old_manifest: &BiBTreeMap<Cid, Type>) -> BiBTreeMap<Cid, Type> {
+    old_manifest
+        .iter()
+        .map(|(_, typ)| (make_identity_cid(format!("new-{}", *typ as i32).as_bytes()), *typ))
+        .collect()
+}
+
+#[test]
+fn migrate_to_same_code() {
+    let (store, root, manifest) = setup();
+    let policy = Default::default();
+
+    let migration = StateMigration::new(&manifest, &manifest).with_workers(2);
+    let (new_root, acc) = migration.migrate_and_check(&store, &policy, PRIOR_EPOCH, &root).unwrap();
+    acc.assert_empty();
+    assert_eq!(root, new_root);
+}
+
+// Counts the blocks written through it.
+struct CountingBlockstore<'a> {
+    store: &'a SyncBlockstore,
+    puts: AtomicUsize,
+}
+
+impl Blockstore for CountingBlockstore<'_> {
+    fn get(&self, k: &Cid) -> anyhow::Result<Option<Vec<u8>>> {
+        self.store.get(k)
+    }
+
+    fn put_keyed(&self, k: &Cid, block: &[u8]) -> anyhow::Result<()> {
+        self.puts.fetch_add(1, Ordering::SeqCst);
+        self.store.put_keyed(k, block)
+    }
+}
+
+#[test]
+fn migration_rewrites_only_changed_subtrees() {
+    // Enough actors for the state tree to have nodes below its root.
+    let (store, root, manifest) = setup_with_accounts(200);
+    let store = CountingBlockstore { store: &store, puts: AtomicUsize::new(0) };
+
+    // No actor changes, so nothing is written back.
+    let migration = StateMigration::new(&manifest, &manifest);
+    let new_root = migration.migrate_state_tree(&store, PRIOR_EPOCH, &root).unwrap();
+    assert_eq!(root, new_root);
+    assert_eq!(0, store.puts.load(Ordering::SeqCst));
+}
+
+// Rewrites account state into a new head, sharing the result between identical states.
+struct AccountMigrator {
+    new_code_cid: Cid,
+}
+
+impl<BS: Blockstore> ActorMigration<BS> for AccountMigrator {
+    fn migrate_state(
+        &self,
+        store: &BS,
+        input: ActorMigrationInput,
+    ) -> anyhow::Result<ActorMigrationOutput> {
+        let state: AccountState = store.get_cbor(&input.head)?.unwrap();
+        let new_head = input.cache.get_or_insert_with(format!("account-{}", input.head), || {
+            store.put_cbor(&AccountState { address: state.address }, Code::Blake2b256)
+        })?;
+        Ok(ActorMigrationOutput { new_code_cid: self.new_code_cid, new_head })
+    }
+}
+
+#[test]
+fn migrate_to_new_code() {
+    let (store, root, old_manifest) = setup();
+    let policy = Default::default();
+
+    let new_manifest = new_code_manifest(&old_manifest);
+    let old_account_code = *old_manifest.get_by_right(&Type::Account).unwrap();
+    let new_account_code = *new_manifest.get_by_right(&Type::Account).unwrap();
+
+    let mut migration = StateMigration::new(&old_manifest, &new_manifest);
+    migration.add_migrator(
+        old_account_code,
+        Box::new(AccountMigrator { new_code_cid: new_account_code }),
+    );
+    let (new_root, acc) = migration.migrate_and_check(&store, &policy, PRIOR_EPOCH, &root).unwrap();
+    acc.assert_empty();
+
+    let old_tree = Tree::load(&store, &root).unwrap();
+    let new_tree = Tree::load(&store, &new_root).unwrap();
+    old_tree
+        .for_each(|address, old| {
+            let new = new_tree.map.get(&address.to_bytes()).unwrap().unwrap();
+            let typ = old_manifest.get_by_left(&old.code).unwrap();
+            assert_eq!(new_manifest.get_by_right(typ).unwrap(), &new.code);
+            assert_eq!(old.balance, new.balance);
+            assert_eq!(old.head, new.head);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn migration_requires_migrator_for_every_actor() {
+    let (store, root, old_manifest) = setup();
+
+    // The new manifest has no account actor, and no migrator is provided.
+    let new_manifest: BiBTreeMap<Cid, Type> = old_manifest
+        .iter()
+        .filter(|(_, typ)| **typ != Type::Account)
+        .map(|(c, t)| (*c, *t))
+        .collect();
+    let migration = StateMigration::new(&old_manifest, &new_manifest);
+    let err = migration.migrate_state_tree(&store, PRIOR_EPOCH, &root).unwrap_err();
+    assert!(err.to_string().contains("no migrator for actor"));
+}
+
+// A multisig transaction as encoded before transactions could expire.
+#[derive(Serialize_tuple, Deserialize_tuple, PartialEq)]
+struct LegacyTransaction {
+    to: Address,
+    value: TokenAmount,
+    method: MethodNum,
+    params: RawBytes,
+    approved: Vec<Address>,
+}
+
+// The expiration the migration gives pending multisig transactions stored without one.
+const MIGRATED_TX_EXPIRATION: ChainEpoch = 1000;
+
+// Sets an expiration on pending multisig transactions stored before expirations were added.
+// Multisigs with the same pending transactions share the migrated map.
+struct MultisigMigrator {
+    new_code_cid: Cid,
+    // Number of pending transaction maps actually migrated, rather than found in the cache.
+    migrated_maps: Arc<AtomicUsize>,
+}
+
+impl<BS: Blockstore> ActorMigration<BS> for MultisigMigrator {
+    fn migrate_state(
+        &self,
+        store: &BS,
+        input: ActorMigrationInput,
+    ) -> anyhow::Result<ActorMigrationOutput> {
+        let state: MultisigState = store.get_cbor(&input.head)?.unwrap();
+        let pending_txs = input.cache.get_or_insert_with(
+            format!("multisig-pending-txs-{}", state.pending_txs),
+            || {
+                self.migrated_maps.fetch_add(1, Ordering::SeqCst);
+                let old = make_map_with_root_and_bitwidth::<_, LegacyTransaction>(
+                    &state.pending_txs,
+                    store,
+                    HAMT_BIT_WIDTH,
+                )?;
+                let mut new = make_empty_map(store, HAMT_BIT_WIDTH);
+                old.for_each(|key, tx| {
+                    new.set(
+                        key.clone(),
+                        Transaction {
+                            to: tx.to,
+                            value: tx.value.clone(),
+                            method: tx.method,
+                            params: tx.params.clone(),
+                            approved: tx.approved.clone(),
+                            expiration: Some(MIGRATED_TX_EXPIRATION),
+                        },
+                    )?;
+                    Ok(())
+                })?;
+                Ok(new.flush()?)
+            },
+        )?;
+        let new_head = store.put_cbor(&MultisigState { pending_txs, ..state }, Code::Blake2b256)?;
+        Ok(ActorMigrationOutput { new_code_cid: self.new_code_cid, new_head })
+    }
+}
+
+fn create_msig(v: &dyn VM<MemoryBlockstore>, signers: &[Address]) -> Address {
+    let params = fil_actor_multisig::ConstructorParams {
+        signers: signers.into(),
+        num_approvals_threshold: 2,
+        unlock_duration: 0,
+        start_epoch: 0,
+    };
+    let ret: ExecReturn = apply_ok(
+        v,
+        &signers[0],
+        &INIT_ACTOR_ADDR,
+        &TokenAmount::zero(),
+        fil_actor_init::Method::Exec as u64,
+        Some(fil_actor_init::ExecParams {
+            code_cid: *MULTISIG_ACTOR_CODE_ID,
+            constructor_params: serialize(&params, "multisig ctor params").unwrap(),
+        }),
+    )
+    .deserialize()
+    .unwrap();
+    ret.id_address
+}
+
+// Returns a state tree with three multisigs, the first of which has a legacy pending transaction.
+fn setup_multisigs() -> (SyncBlockstore, Cid, BiBTreeMap<Cid, Type>, Vec<Address>) {
+    let store = MemoryBlockstore::new();
+    let (root, manifest, msigs) = {
+        let v = TestVM::<MemoryBlockstore>::new_with_singletons(&store);
+        let signers = create_accounts(&v, 2, &TokenAmount::from_whole(10_000));
+        let signer = signers[0];
+        let msigs: Vec<_> = (0..3).map(|_| create_msig(&v, &signers)).collect();
+
+        let legacy = LegacyTransaction {
+            to: signer,
+            value: TokenAmount::from_atto(1),
+            method: METHOD_SEND,
+            params: RawBytes::default(),
+            approved: vec![signer],
+        };
+        let mut pending = make_empty_map(&store, HAMT_BIT_WIDTH);
+        pending.set(TxnID(0).key(), legacy).unwrap();
+        let pending_txs = pending.flush().unwrap();
+
+        let state: MultisigState = get_state(&v, &msigs[0]).unwrap();
+        let mut actor = v.get_actor(&msigs[0]).unwrap();
+        actor.head = v.put_store(&MultisigState { pending_txs, next_tx_id: TxnID(1), ..state });
+        v.set_actor(&msigs[0], actor);
+        (v.checkpoint(), v.actor_manifest(), msigs)
+    };
+    (SyncBlockstore(Mutex::new(store)), root, manifest, msigs)
+}
+
+#[test]
+fn migrate_rewrites_state() {
+    let (store, root, old_manifest, msigs) = setup_multisigs();
+    let policy = Default::default();
+    let new_manifest = new_code_manifest(&old_manifest);
+
+    let migrated_maps = Arc::new(AtomicUsize::new(0));
+    // A single worker, so concurrent migrations don't race to fill the cache.
+    let mut migration = StateMigration::new(&old_manifest, &new_manifest).with_workers(1);
+    migration.add_migrator(
+        *old_manifest.get_by_right(&Type::Multisig).unwrap(),
+        Box::new(MultisigMigrator {
+            new_code_cid: *new_manifest.get_by_right(&Type::Multisig).unwrap(),
+            migrated_maps: migrated_maps.clone(),
+        }),
+    );
+    let (new_root, acc) = migration.migrate_and_check(&store, &policy, PRIOR_EPOCH, &root).unwrap();
+    acc.assert_empty();
+
+    // The two multisigs with no pending transactions shared a single migration of their map.
+    assert_eq!(2, migrated_maps.load(Ordering::SeqCst));
+
+    let old_tree = Tree::load(&store, &root).unwrap();
+    let new_tree = Tree::load(&store, &new_root).unwrap();
+    let load_msig = |tree: &Tree<SyncBlockstore>, addr: &Address| -> MultisigState {
+        let actor = tree.map.get(&addr.to_bytes()).unwrap().unwrap();
+        store.get_cbor(&actor.head).unwrap().unwrap()
+    };
+
+    // The legacy transaction is given an expiration.
+    let old_state = load_msig(&old_tree, &msigs[0]);
+    let new_state = load_msig(&new_tree, &msigs[0]);
+    assert_ne!(old_state.pending_txs, new_state.pending_txs);
+    let new_pending = make_map_with_root_and_bitwidth::<_, Transaction>(
+        &new_state.pending_txs,
+        &store,
+        HAMT_BIT_WIDTH,
+    )
+    .unwrap();
+    let tx = new_pending.get(&TxnID(0).key()).unwrap().unwrap();
+    let mut expected = make_empty_map(&store, HAMT_BIT_WIDTH);
+    expected.set(TxnID(0).key(), tx.clone()).unwrap();
+    assert_eq!(expected.flush().unwrap(), new_state.pending_txs);
+    assert_eq!(Some(MIGRATED_TX_EXPIRATION), tx.expiration);
+    assert_eq!(TokenAmount::from_atto(1), tx.value);
+
+    // Multisigs with the same pending transactions share the migrated map.
+    assert_eq!(
+        load_msig(&new_tree, &msigs[1]).pending_txs,
+        load_msig(&new_tree, &msigs[2]).pending_txs
+    );
+}
+
+#[test]
+fn migrate_versioned_state_root() {
+    let (store, root, old_manifest) = setup();
+    let new_manifest = new_code_manifest(&old_manifest);
+    let info = store.put_cbor(&(), Code::Blake2b256).unwrap();
+    let state_root =
+        store.put_cbor(&StateRoot { version: 4, actors: root, info }, Code::Blake2b256).unwrap();
+
+    let migration = StateMigration::new(&old_manifest, &new_manifest);
+    let new_state_root = migration.migrate_state_root(&store, PRIOR_EPOCH, &state_root, 5).unwrap();
+    let new: StateRoot = store.get_cbor(&new_state_root).unwrap().unwrap();
+    assert_eq!(5, new.version);
+    assert_eq!(info, new.info);
+    assert_eq!(migration.migrate_state_tree(&store, PRIOR_EPOCH, &root).unwrap(), new.actors);
+}
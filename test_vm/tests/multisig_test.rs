@@ -44,6 +44,7 @@ fn proposal_hash_test<BS: Blockstore>(v: &dyn VM<BS>, addrs: &[Address]) -> Toke
         value: fil_delta.clone(),
         method: METHOD_SEND,
         params: RawBytes::default(),
+        expiration: None,
     };
     apply_ok(
         v,
@@ -60,6 +61,7 @@ fn proposal_hash_test<BS: Blockstore>(v: &dyn VM<BS>, addrs: &[Address]) -> Toke
         method: METHOD_SEND,
         approved: vec![alice],
         params: RawBytes::default(),
+        expiration: None,
     };
     let wrong_hash = compute_proposal_hash(&wrong_tx, &MockRuntime::new(*v.blockstore())).unwrap();
     let wrong_approval_params = TxnIDParams { id: TxnID(0), proposal_hash: wrong_hash.to_vec() };
@@ -79,6 +81,7 @@ fn proposal_hash_test<BS: Blockstore>(v: &dyn VM<BS>, addrs: &[Address]) -> Toke
         method: METHOD_SEND,
         approved: vec![alice],
         params: RawBytes::default(),
+        expiration: None,
     };
     let correct_hash =
         compute_proposal_hash(&correct_tx, &MockRuntime::new(*v.blockstore())).unwrap();
@@ -123,6 +126,7 @@ fn test_delete_self() {
             value: TokenAmount::zero(),
             method: MsigMethod::RemoveSigner as u64,
             params: remove_param_ser,
+            expiration: None,
         };
 
         // first proposal goes ok and should have txnid = 0
@@ -196,6 +200,7 @@ fn swap_self_1_of_2_test<BS: Blockstore>(v: &dyn VM<BS>) {
         value: TokenAmount::zero(),
         method: MsigMethod::SwapSigner as u64,
         params: serialize(&swap_params, "swap params").unwrap(),
+        expiration: None,
     };
     // alice succeeds when trying to execute the tx swapping alice for chuck
     apply_ok(
@@ -231,6 +236,7 @@ fn swap_self_2_of_3_test<BS: Blockstore>(v: &dyn VM<BS>) {
         value: TokenAmount::zero(),
         method: MsigMethod::SwapSigner as u64,
         params: serialize(&swap_params, "swap params").unwrap(),
+        expiration: None,
     };
 
     // proposal from swapped addr goes ok with txnid 0
@@ -263,6 +269,7 @@ fn swap_self_2_of_3_test<BS: Blockstore>(v: &dyn VM<BS>) {
         value: TokenAmount::zero(),
         method: MsigMethod::SwapSigner as u64,
         params: serialize(&swap_params, "swap params").unwrap(),
+        expiration: None,
     };
 
     // proposal from non swapped goes ok, txnid = 1
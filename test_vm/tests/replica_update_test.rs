@@ -327,6 +327,32 @@ fn prove_replica_update_multi_dline_test<BS: Blockstore>(v: &dyn VM<BS>) {
     .deserialize()
     .unwrap();
 
+    // The test VM computes the same unsealed CID for any pieces.
+    let unsealed_cid = Some(make_piece_cid(b"unsealed from itest vm"));
+    ExpectInvocation {
+        from: worker,
+        to: maddr,
+        method: MinerMethod::ProveReplicaUpdates as u64,
+        events: Some(vec![
+            Expect::build_sector_event(
+                "sector-updated",
+                maddr.id().unwrap(),
+                first_sector_number_p1,
+                unsealed_cid,
+                &deal_ids[0..1],
+            ),
+            Expect::build_sector_event(
+                "sector-updated",
+                maddr.id().unwrap(),
+                first_sector_number_p2,
+                unsealed_cid,
+                &deal_ids[1..],
+            ),
+        ]),
+        ..Default::default()
+    }
+    .matches(v.take_invocations().last().unwrap());
+
     assert_eq!(ret_bf.len(), 2);
     assert!(ret_bf.get(first_sector_number_p1));
     assert!(ret_bf.get(first_sector_number_p2));
@@ -1204,7 +1230,15 @@ fn replica_update_verified_deal_test<BS: Blockstore>(v: &dyn VM<BS>) {
         to: maddr,
         method: MinerMethod::ProveReplicaUpdates2 as u64,
         subinvocs: Some(vec![
-            Expect::market_activate_deals(maddr, deal_ids.clone(), old_sector_info.expiration),
+            ExpectInvocation {
+                events: Some(vec![Expect::build_market_event(
+                    "deal-activated",
+                    deal_ids[0],
+                    client.id().unwrap(),
+                    maddr.id().unwrap(),
+                )]),
+                ..Expect::market_activate_deals(maddr, deal_ids.clone(), old_sector_info.expiration)
+            },
             ExpectInvocation {
                 from: maddr,
                 to: VERIFIED_REGISTRY_ACTOR_ADDR,
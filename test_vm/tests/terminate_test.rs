@@ -270,7 +270,23 @@ fn terminate_sectors_test<BS: Blockstore>(v: &dyn VM<BS>) {
             Expect::power_current_total(miner_id_addr),
             Expect::burn(miner_id_addr, None),
             Expect::power_update_pledge(miner_id_addr, None),
-            Expect::market_sectors_terminate(miner_id_addr, epoch, deal_ids.clone()),
+            ExpectInvocation {
+                events: Some(
+                    deal_ids
+                        .iter()
+                        .zip([verified_client, verified_client, unverified_client])
+                        .map(|(id, client)| {
+                            Expect::build_market_event(
+                                "deal-terminated",
+                                *id,
+                                client.id().unwrap(),
+                                miner_id_addr.id().unwrap(),
+                            )
+                        })
+                        .collect(),
+                ),
+                ..Expect::market_sectors_terminate(miner_id_addr, epoch, deal_ids.clone())
+            },
             Expect::power_update_claim(miner_id_addr, sector_power.neg()),
         ]),
         ..Default::default()